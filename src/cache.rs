@@ -0,0 +1,146 @@
+// File: src/cache.rs
+// --- Local cache of scripts downloaded from registries
+// --- Layout: <cache>/registry/<registry-key>/<codename>/<version>.tskln (+ .sha256 sidecar)
+
+use std::path::{Path, PathBuf};
+use crate::constants::TASKLINE_EXTENSION;
+use crate::lockfile::checksum;
+use crate::registry::Registry;
+use crate::{TasklineError, Version};
+
+/// Overrides the cache location
+pub const CACHE_DIR_ENV_VAR: &str = "TASKLINE_CACHE_DIR";
+
+/// Cached scripts of a single registry
+#[derive(Debug, Clone)]
+pub struct ScriptCache {
+    root: PathBuf,
+}
+
+impl ScriptCache {
+    /// Platform cache directory: `$TASKLINE_CACHE_DIR`, `$XDG_CACHE_HOME/taskline`,
+    /// `~/.cache/taskline`, or `%LOCALAPPDATA%\taskline` on Windows
+    pub fn default_dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV_VAR) {
+            return Some(PathBuf::from(dir));
+        }
+        if cfg!(windows) {
+            return std::env::var_os("LOCALAPPDATA").map(|dir| PathBuf::from(dir).join("taskline"));
+        }
+        if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+            return Some(PathBuf::from(dir).join("taskline"));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("taskline"))
+    }
+
+    /// Cache for `registry` under the default cache directory
+    pub fn for_registry(registry: &Registry) -> Result<Self, TasklineError> {
+        let base = Self::default_dir()
+            .ok_or_else(|| TasklineError::FileError("Could not determine a cache directory; set TASKLINE_CACHE_DIR".to_string()))?;
+        Ok(Self::in_dir(&base, registry))
+    }
+
+    /// Cache for `registry` under an explicit base directory
+    pub fn in_dir(base: &Path, registry: &Registry) -> Self {
+        Self { root: base.join("registry").join(registry_key(registry)) }
+    }
+
+    /// Store downloaded content together with its checksum
+    pub fn store(&self, codename: &str, version: Version, content: &str) -> Result<(), TasklineError> {
+        let path = self.script_path(codename, version);
+        let io = |e: std::io::Error| TasklineError::FileError(format!("{}: {}", path.display(), e));
+        std::fs::create_dir_all(path.parent().unwrap_or(&self.root)).map_err(io)?;
+        std::fs::write(&path, content).map_err(io)?;
+        std::fs::write(checksum_path(&path), checksum(content)).map_err(io)
+    }
+
+    /// Cached content, `None` when not cached; errors when the entry fails its integrity check
+    pub fn load(&self, codename: &str, version: Version) -> Result<Option<String>, TasklineError> {
+        let path = self.script_path(codename, version);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(TasklineError::FileError(format!("{}: {}", path.display(), e))),
+        };
+        let expected = std::fs::read_to_string(checksum_path(&path)).unwrap_or_default();
+        if expected.trim() != checksum(&content) {
+            return Err(TasklineError::FileError(format!(
+                "Cached {} {} is corrupted ({}); pull again while online", codename, version, path.display()
+            )));
+        }
+        Ok(Some(content))
+    }
+
+    /// Versions of `codename` present in the cache, ascending
+    pub fn versions(&self, codename: &str) -> Vec<Version> {
+        let Ok(entries) = std::fs::read_dir(self.root.join(codename)) else { return Vec::new() };
+        let mut versions: Vec<Version> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let stem = name.strip_suffix(TASKLINE_EXTENSION)?.strip_suffix('.')?;
+                Version::parse(stem).ok()
+            })
+            .collect();
+        versions.sort();
+        versions
+    }
+
+    fn script_path(&self, codename: &str, version: Version) -> PathBuf {
+        self.root.join(codename).join(format!("{}.{}", version, TASKLINE_EXTENSION))
+    }
+}
+
+fn checksum_path(script: &Path) -> PathBuf {
+    let mut path = script.as_os_str().to_owned();
+    path.push(".sha256");
+    PathBuf::from(path)
+}
+
+/// Stable directory name identifying a registry location
+fn registry_key(registry: &Registry) -> String {
+    let identity = match registry {
+        Registry::Directory(path) => format!("dir:{}", path.display()),
+        Registry::Git { url, branch } => format!("git:{}#{}", url, branch.as_deref().unwrap_or("")),
+    };
+    // "sha256:" prefix dropped; 16 hex chars are plenty to keep registries apart
+    checksum(&identity)[7..23].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_store_load_and_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScriptCache::in_dir(temp_dir.path(), &Registry::Directory("/srv/registry".into()));
+
+        assert_eq!(cache.load("deploy", Version::new(1, 0, 0)).unwrap(), None);
+        cache.store("deploy", Version::new(1, 0, 0), "one").unwrap();
+        cache.store("deploy", Version::new(1, 10, 0), "ten").unwrap();
+        assert_eq!(cache.load("deploy", Version::new(1, 10, 0)).unwrap().as_deref(), Some("ten"));
+        assert_eq!(cache.versions("deploy"), vec![Version::new(1, 0, 0), Version::new(1, 10, 0)]);
+        assert!(cache.versions("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_corruption_is_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ScriptCache::in_dir(temp_dir.path(), &Registry::Directory("/srv/registry".into()));
+        cache.store("deploy", Version::new(1, 0, 0), "original").unwrap();
+
+        std::fs::write(cache.script_path("deploy", Version::new(1, 0, 0)), "tampered").unwrap();
+        assert!(cache.load("deploy", Version::new(1, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_registries_are_isolated() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = ScriptCache::in_dir(temp_dir.path(), &Registry::Directory("/srv/a".into()));
+        let b = ScriptCache::in_dir(temp_dir.path(), &Registry::Git { url: "https://example.com/b.git".into(), branch: None });
+        a.store("deploy", Version::new(1, 0, 0), "from a").unwrap();
+        assert_eq!(b.load("deploy", Version::new(1, 0, 0)).unwrap(), None);
+    }
+}
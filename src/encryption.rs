@@ -0,0 +1,173 @@
+// File: src/encryption.rs
+// --- age encryption for `.tskbe` scripts
+// --- Encrypted scripts are decrypted transparently by `read_script`
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+use age::secrecy::SecretString;
+use crate::TasklineError;
+
+/// Path of an age identity file used to decrypt scripts
+pub const IDENTITY_ENV_VAR: &str = "TASKLINE_IDENTITY";
+/// Passphrase for passphrase-encrypted scripts (skips the prompt)
+pub const PASSPHRASE_ENV_VAR: &str = "TASKLINE_PASSPHRASE";
+
+const AGE_MAGIC: &[u8] = b"age-encryption.org/v1";
+
+/// How a script should be decrypted
+pub enum DecryptionKey {
+    /// age identity file (`AGE-SECRET-KEY-1...` lines)
+    IdentityFile(std::path::PathBuf),
+    /// A single `AGE-SECRET-KEY-1...` secret
+    Secret(String),
+    /// Passphrase for scrypt-encrypted files
+    Passphrase(String),
+}
+
+/// Whether `bytes` look like an age-encrypted file
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(AGE_MAGIC)
+}
+
+/// Encrypt script content to one or more `age1...` recipients
+pub fn encrypt(content: &str, recipients: &[String]) -> Result<Vec<u8>, TasklineError> {
+    let parsed = recipients
+        .iter()
+        .map(|r| age::x25519::Recipient::from_str(r)
+            .map_err(|e| TasklineError::EncryptionError(format!("Invalid recipient '{}': {}", r, e))))
+        .collect::<Result<Vec<_>, _>>()?;
+    let encryptor = age::Encryptor::with_recipients(parsed.iter().map(|r| r as &dyn age::Recipient))
+        .map_err(|e| TasklineError::EncryptionError(e.to_string()))?;
+    write_encrypted(encryptor, content)
+}
+
+/// Encrypt script content with a passphrase
+pub fn encrypt_with_passphrase(content: &str, passphrase: &str) -> Result<Vec<u8>, TasklineError> {
+    write_encrypted(age::Encryptor::with_user_passphrase(SecretString::from(passphrase.to_string())), content)
+}
+
+fn write_encrypted(encryptor: age::Encryptor, content: &str) -> Result<Vec<u8>, TasklineError> {
+    let mut output = Vec::with_capacity(content.len() + 256);
+    let io = |e: std::io::Error| TasklineError::EncryptionError(e.to_string());
+    let mut writer = encryptor.wrap_output(&mut output).map_err(io)?;
+    writer.write_all(content.as_bytes()).map_err(io)?;
+    writer.finish().map_err(io)?;
+    Ok(output)
+}
+
+/// Whether an encrypted file was sealed with a passphrase rather than recipients
+pub fn is_passphrase_encrypted(bytes: &[u8]) -> Result<bool, TasklineError> {
+    let decryptor = age::Decryptor::new_buffered(bytes)
+        .map_err(|e| TasklineError::EncryptionError(e.to_string()))?;
+    Ok(decryptor.is_scrypt())
+}
+
+/// Decrypt an age-encrypted script
+pub fn decrypt(bytes: &[u8], key: &DecryptionKey) -> Result<String, TasklineError> {
+    let err = |e: &dyn std::fmt::Display| TasklineError::EncryptionError(e.to_string());
+    let identities: Vec<Box<dyn age::Identity>> = match key {
+        DecryptionKey::IdentityFile(path) => age::IdentityFile::from_file(path.to_string_lossy().into_owned())
+            .map_err(|e| TasklineError::EncryptionError(format!("{}: {}", path.display(), e)))?
+            .into_identities()
+            .map_err(|e| err(&e))?,
+        DecryptionKey::Secret(secret) => vec![Box::new(
+            age::x25519::Identity::from_str(secret.trim()).map_err(|e| err(&e))?,
+        )],
+        DecryptionKey::Passphrase(passphrase) => vec![Box::new(
+            age::scrypt::Identity::new(SecretString::from(passphrase.clone())),
+        )],
+    };
+
+    let decryptor = age::Decryptor::new_buffered(bytes).map_err(|e| err(&e))?;
+    let mut reader = decryptor
+        .decrypt(identities.iter().map(|i| i.as_ref() as &dyn age::Identity))
+        .map_err(|e| err(&e))?;
+    let mut content = String::new();
+    reader.read_to_string(&mut content).map_err(|e| err(&e))?;
+    Ok(content)
+}
+
+/// Read a script, transparently decrypting `.tskbe`/age content.
+/// The key comes from `TASKLINE_IDENTITY`/`TASKLINE_PASSPHRASE`, or a prompt when `interactive`.
+pub fn read_script(path: &Path, interactive: bool) -> Result<String, TasklineError> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| TasklineError::FileError(format!("{}: {}", path.display(), e)))?;
+    if !is_encrypted(&bytes) {
+        return String::from_utf8(bytes)
+            .map_err(|_| TasklineError::FileError(format!("{}: not valid UTF-8", path.display())));
+    }
+    let key = key_from_env_or_prompt(path, is_passphrase_encrypted(&bytes)?, interactive)?;
+    decrypt(&bytes, &key)
+}
+
+/// Resolve the decryption key without asking when the environment already provides one
+pub fn key_from_env_or_prompt(path: &Path, passphrase: bool, interactive: bool) -> Result<DecryptionKey, TasklineError> {
+    if passphrase {
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+            return Ok(DecryptionKey::Passphrase(passphrase));
+        }
+    } else if let Some(identity) = std::env::var_os(IDENTITY_ENV_VAR) {
+        return Ok(DecryptionKey::IdentityFile(identity.into()));
+    }
+
+    if !interactive {
+        return Err(TasklineError::EncryptionError(format!(
+            "{} is encrypted; set {} to decrypt it non-interactively",
+            path.display(),
+            if passphrase { PASSPHRASE_ENV_VAR } else { IDENTITY_ENV_VAR }
+        )));
+    }
+
+    let prompt = if passphrase {
+        format!("Passphrase for {}: ", path.display())
+    } else {
+        format!("Secret key (AGE-SECRET-KEY-1...) for {}: ", path.display())
+    };
+    let answer = rpassword::prompt_password(prompt)
+        .map_err(|e| TasklineError::EncryptionError(format!("Failed to read key: {}", e)))?;
+    Ok(if passphrase { DecryptionKey::Passphrase(answer) } else { DecryptionKey::Secret(answer) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = "@Taskline codename deploy\n@Taskline version v1.0.0\n\n-> secret steps\n";
+
+    #[test]
+    fn test_recipient_round_trip() {
+        let identity = age::x25519::Identity::generate();
+        let recipient = identity.to_public().to_string();
+        let secret = {
+            use age::secrecy::ExposeSecret;
+            identity.to_string().expose_secret().to_string()
+        };
+
+        let encrypted = encrypt(SCRIPT, &[recipient]).unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_passphrase_encrypted(&encrypted).unwrap());
+        assert_eq!(decrypt(&encrypted, &DecryptionKey::Secret(secret)).unwrap(), SCRIPT);
+
+        let other = age::x25519::Identity::generate();
+        let other_secret = {
+            use age::secrecy::ExposeSecret;
+            other.to_string().expose_secret().to_string()
+        };
+        assert!(decrypt(&encrypted, &DecryptionKey::Secret(other_secret)).is_err());
+    }
+
+    #[test]
+    fn test_passphrase_round_trip() {
+        let encrypted = encrypt_with_passphrase(SCRIPT, "correct horse").unwrap();
+        assert!(is_passphrase_encrypted(&encrypted).unwrap());
+        assert_eq!(decrypt(&encrypted, &DecryptionKey::Passphrase("correct horse".into())).unwrap(), SCRIPT);
+        assert!(decrypt(&encrypted, &DecryptionKey::Passphrase("wrong".into())).is_err());
+    }
+
+    #[test]
+    fn test_invalid_recipient_and_plain_content() {
+        assert!(encrypt(SCRIPT, &["not-a-recipient".to_string()]).is_err());
+        assert!(!is_encrypted(SCRIPT.as_bytes()));
+    }
+}
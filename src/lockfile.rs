@@ -0,0 +1,164 @@
+// File: src/lockfile.rs
+// --- `taskline.lock`: exact versions and checksums of pulled scripts
+// --- Lives next to `.taskline.toml` so a whole repository shares one lock
+
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::{TasklineError, Version, VersionReq};
+
+/// Name of the lockfile written next to the repository config
+pub const LOCKFILE_NAME: &str = "taskline.lock";
+
+const LOCKFILE_FORMAT: u32 = 1;
+const HEADER: &str = "# This file is generated by taskline. Do not edit it by hand.\n\n";
+
+/// One pulled script pinned to an exact version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedScript {
+    pub codename: String,
+    /// The requirement the version was resolved from (`*`, `^2.1`, `=1.0.0`)
+    pub requirement: String,
+    pub version: String,
+    pub checksum: String,
+}
+
+/// Parsed `taskline.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "script")]
+    pub scripts: Vec<LockedScript>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self { version: LOCKFILE_FORMAT, scripts: Vec::new() }
+    }
+}
+
+impl Lockfile {
+    /// Path of the lockfile for a repository rooted at `root`
+    pub fn path_in(root: &Path) -> PathBuf {
+        root.join(LOCKFILE_NAME)
+    }
+
+    /// Load the lockfile, or an empty one when it does not exist yet
+    pub fn load(path: &Path) -> Result<Self, TasklineError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(TasklineError::FileError(format!("{}: {}", path.display(), e))),
+        };
+        let lockfile: Lockfile = toml::from_str(&content)
+            .map_err(|e| TasklineError::ParseError(format!("{}: {}", path.display(), e)))?;
+        if lockfile.version != LOCKFILE_FORMAT {
+            return Err(TasklineError::ParseError(format!(
+                "{}: unsupported lockfile version {}", path.display(), lockfile.version
+            )));
+        }
+        Ok(lockfile)
+    }
+
+    /// Write the lockfile with entries sorted by codename for stable diffs
+    pub fn save(&self, path: &Path) -> Result<(), TasklineError> {
+        let mut sorted = self.clone();
+        sorted.scripts.sort_by(|a, b| a.codename.cmp(&b.codename));
+        let body = toml::to_string(&sorted)
+            .map_err(|e| TasklineError::ParseError(format!("Failed to serialize lockfile: {}", e)))?;
+        std::fs::write(path, format!("{}{}", HEADER, body))
+            .map_err(|e| TasklineError::FileError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Locked entry for `codename`
+    pub fn get(&self, codename: &str) -> Option<&LockedScript> {
+        self.scripts.iter().find(|s| s.codename == codename)
+    }
+
+    /// Insert or replace the entry for a script
+    pub fn record(&mut self, codename: &str, requirement: &VersionReq, version: Version, content: &str) {
+        let entry = LockedScript {
+            codename: codename.to_string(),
+            requirement: requirement.to_string(),
+            version: version.to_string(),
+            checksum: checksum(content),
+        };
+        match self.scripts.iter_mut().find(|s| s.codename == codename) {
+            Some(existing) => *existing = entry,
+            None => self.scripts.push(entry),
+        }
+    }
+}
+
+impl LockedScript {
+    /// The pinned version
+    pub fn locked_version(&self) -> Result<Version, TasklineError> {
+        Version::parse(&self.version)
+    }
+
+    /// Fail unless `content` is byte-identical to what was locked
+    pub fn verify(&self, content: &str) -> Result<(), TasklineError> {
+        let actual = checksum(content);
+        if actual != self.checksum {
+            return Err(TasklineError::RegistryError(format!(
+                "Checksum mismatch for {} {}: locked {}, registry has {}",
+                self.codename, self.version, self.checksum, actual
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// `sha256:<hex>` digest of script content
+pub fn checksum(content: &str) -> String {
+    let digest = Sha256::digest(content.as_bytes());
+    let mut hex = String::with_capacity(7 + digest.len() * 2);
+    hex.push_str("sha256:");
+    for byte in digest {
+        hex.push_str(&format!("{:02x}", byte));
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(""), "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    }
+
+    #[test]
+    fn test_record_save_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Lockfile::path_in(temp_dir.path());
+        assert_eq!(Lockfile::load(&path).unwrap(), Lockfile::default());
+
+        let mut lockfile = Lockfile::default();
+        lockfile.record("zeta", &VersionReq::STAR, Version::new(1, 0, 0), "z");
+        lockfile.record("deploy", &VersionReq::parse("^2.1").unwrap(), Version::new(2, 1, 0), "old");
+        lockfile.record("deploy", &VersionReq::parse("^2.1").unwrap(), Version::new(2, 4, 0), "new");
+        lockfile.save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# This file is generated by taskline"));
+        assert!(content.find("deploy").unwrap() < content.find("zeta").unwrap());
+
+        let loaded = Lockfile::load(&path).unwrap();
+        let deploy = loaded.get("deploy").unwrap();
+        assert_eq!(deploy.requirement, "^2.1");
+        assert_eq!(deploy.locked_version().unwrap(), Version::new(2, 4, 0));
+        assert!(deploy.verify("new").is_ok());
+        assert!(deploy.verify("tampered").is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Lockfile::path_in(temp_dir.path());
+        std::fs::write(&path, "version = 99\n").unwrap();
+        assert!(Lockfile::load(&path).is_err());
+    }
+}
@@ -1,6 +1,16 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use std::process::{Command, exit};
+use std::time::Duration;
 use tokio::process::Command as AsyncCommand;
+use taskline::config::TasklineConfig;
+use taskline::lockfile::LOCKFILE_NAME;
+use taskline::pull::{PullOptions, PullSource, PullSpec};
+use taskline::registry::Registry;
+use taskline::workspace::write_new_file;
+use taskline::{Codename, TasklineError, Version};
+
+/// How long a component gets to exit after a forwarded shutdown signal
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
 
 #[derive(Parser)]
 #[command(name = "taskline")]
@@ -8,6 +18,9 @@ use tokio::process::Command as AsyncCommand;
 #[command(version = "0.1.0")]
 #[command(author = "d33p0st")]
 struct Cli {
+    /// Non-interactive mode with plain, stable output (auto-detected from CI env vars)
+    #[arg(long, global = true)]
+    ci: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -21,15 +34,165 @@ enum Commands {
         filename: String,
         /// Optional version (e.g., v1.0.0)
         version: Option<String>,
+        /// Author name for `@Taskline author` (defaults to git's user.name)
+        #[arg(long)]
+        author: Option<String>,
+        /// Author email for `@Taskline author` (defaults to git's user.email)
+        #[arg(long)]
+        email: Option<String>,
+        /// Template file rendered with {{codename}}, {{version}}, {{author}}, {{date}} and --var values
+        #[arg(long)]
+        template: Option<String>,
+        /// Extra template variable as key=value; repeatable
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
     },
     /// Bump version of an existing taskline script
     #[command(name = "bump")]
     Bump {
         /// Script file to bump version for
-        filename: String,
+        #[arg(required_unless_present = "changed_since")]
+        filename: Option<String>,
         /// Version bump type
         #[arg(value_enum)]
         bump_type: Option<BumpType>,
+        /// Choose the bump type from conventional commits since the script's version tag
+        #[arg(long, conflicts_with = "bump_type")]
+        auto: bool,
+        /// Bump every script changed since this git ref instead of one file
+        #[arg(long, value_name = "GIT_REF", conflicts_with = "filename")]
+        changed_since: Option<String>,
+    },
+    /// Bump a script, add a CHANGELOG.md entry, then commit and tag the release
+    #[command(name = "release")]
+    Release {
+        /// Script file to release
+        filename: String,
+        /// Version bump type; chosen from conventional commits when omitted
+        #[arg(value_enum)]
+        bump_type: Option<BumpType>,
+        /// Push the release commit and tag to origin
+        #[arg(long)]
+        push: bool,
+        /// Print what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Publish a versioned script to the configured registry
+    #[command(name = "publish")]
+    Publish {
+        /// Script file to publish
+        filename: String,
+    },
+    /// Download a script from the configured registry
+    #[command(name = "pull")]
+    Pull {
+        /// Script to pull: codename, codename@[v]X.Y.Z, or codename@<constraint> such as deploy@^2.1
+        spec: String,
+        /// Require the version pinned in taskline.lock instead of resolving a new one
+        #[arg(long)]
+        locked: bool,
+        /// Use only the local cache, never contacting the registry
+        #[arg(long)]
+        offline: bool,
+        /// Fail instead of warning when the script is marked `@Taskline deprecated`
+        #[arg(long)]
+        deny_deprecated: bool,
+        /// Accept a script whose `@Taskline expires` date has passed
+        #[arg(long)]
+        ignore_expiry: bool,
+    },
+    /// Encrypt a script into a .tskbe file with age
+    #[command(name = "encrypt")]
+    Encrypt {
+        /// Script file to encrypt
+        filename: String,
+        /// age recipient (age1...) allowed to decrypt; repeatable
+        #[arg(short, long = "recipient", required_unless_present = "passphrase")]
+        recipients: Vec<String>,
+        /// Encrypt with a passphrase instead (read from TASKLINE_PASSPHRASE or prompted)
+        #[arg(long, conflicts_with = "recipients")]
+        passphrase: bool,
+    },
+    /// Decrypt a .tskbe file back into a plain .tskln script
+    #[command(name = "decrypt")]
+    Decrypt {
+        /// Encrypted script to decrypt
+        filename: String,
+        /// age identity file (defaults to TASKLINE_IDENTITY, otherwise prompts for the key)
+        #[arg(short, long)]
+        identity: Option<std::path::PathBuf>,
+    },
+    /// Show a script's header metadata
+    #[command(name = "info")]
+    Info {
+        /// Script file (.tskln or .tskbe)
+        filename: String,
+    },
+    /// Rewrite a script's header between `@Taskline` lines and YAML frontmatter
+    #[command(name = "convert")]
+    Convert {
+        /// Script file to convert in place
+        filename: String,
+        /// Header format to convert to
+        #[arg(long, value_enum)]
+        to: HeaderFormat,
+    },
+    /// Print the highest-versioned file matching a pattern such as 'scripts/deploy*'
+    #[command(name = "latest")]
+    Latest {
+        /// File name pattern (`*` and `?`); quote it so the shell does not expand it
+        pattern: String,
+    },
+    /// Delete all but the newest versioned copies of each script matching a pattern
+    #[command(name = "gc")]
+    Gc {
+        /// File name pattern (`*` and `?`); quote it so the shell does not expand it
+        pattern: String,
+        /// Versions to keep per script
+        #[arg(long, default_value = "3")]
+        keep: std::num::NonZeroUsize,
+        /// Only list the files that would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print a script's metadata as JSON, TOML or YAML, in the shape of `taskline schema`
+    #[command(name = "export-meta")]
+    ExportMeta {
+        /// Script file (.tskln or .tskbe)
+        filename: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: MetaFormat,
+    },
+    /// Build deb/rpm packages and a Homebrew formula from the built binaries
+    #[command(name = "package")]
+    Package {
+        /// Package formats to build (comma separated)
+        #[arg(long = "format", value_enum, value_delimiter = ',', required = true)]
+        formats: Vec<PackageFormat>,
+        /// Directory holding the built taskline, taskline-init and taskline-bump binaries
+        #[arg(long, default_value = "target/release")]
+        bin_dir: std::path::PathBuf,
+        /// Where packages are written
+        #[arg(long, default_value = "dist")]
+        out_dir: std::path::PathBuf,
+        /// Address the Homebrew tarball will be published under, e.g. a release download URL
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Print the JSON Schema of script metadata, or check a metadata JSON file against it
+    #[command(name = "schema")]
+    Schema {
+        /// Metadata JSON file to validate instead ("-" reads stdin)
+        #[arg(long, value_name = "FILE")]
+        validate: Option<String>,
+    },
+    /// Re-resolve scripts pinned in taskline.lock to their newest matching versions
+    #[command(name = "update")]
+    Update {
+        /// Only update this codename
+        codename: Option<String>,
     },
     /// Install all Taskline components (taskline-init, taskline-bump)
     #[command(name = "install")]
@@ -40,7 +203,41 @@ enum Commands {
     },
     /// Check status of Taskline components
     #[command(name = "doctor")]
-    Doctor,
+    Doctor {
+        /// Script whose `@Taskline requires` tools should also be verified
+        file: Option<String>,
+    },
+    /// Validate script headers
+    #[command(name = "validate")]
+    Validate {
+        /// Scripts to validate
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        files: Vec<String>,
+        /// Validate every .tskln file in the workspace, in parallel
+        #[arg(long)]
+        all: bool,
+        /// Number of files validated at once (defaults to the number of CPUs)
+        #[arg(long, short)]
+        jobs: Option<usize>,
+    },
+    /// Manage opt-in anonymous usage telemetry, kept locally and never transmitted
+    #[command(name = "telemetry")]
+    Telemetry {
+        #[arg(value_enum)]
+        action: TelemetryAction,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum TelemetryAction {
+    /// Start recording command counts and error categories locally
+    On,
+    /// Stop recording and delete what was collected
+    Off,
+    /// Show whether telemetry is enabled and what was collected
+    Status,
+    /// Print what was collected as JSON, to attach to an issue or send by hand
+    Export,
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -50,70 +247,349 @@ enum BumpType {
     Patch,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum HeaderFormat {
+    /// `@Taskline` lines
+    Header,
+    /// `--- ... ---` YAML frontmatter
+    Frontmatter,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum MetaFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum PackageFormat {
+    Deb,
+    Rpm,
+    Homebrew,
+}
+
+/// Output style shared by all dispatcher messages
+#[derive(Clone, Copy)]
+struct Ui {
+    ci: bool,
+}
+
+impl Ui {
+    /// Pick the emoji marker, or its ASCII replacement in CI mode
+    #[inline(always)]
+    fn mark(self, emoji: &'static str, plain: &'static str) -> &'static str {
+        if self.ci { plain } else { emoji }
+    }
+
+    /// Propagate CI mode to a child process
+    fn configure(self, cmd: &mut Command) {
+        if self.ci {
+            cmd.env(taskline::ci::CI_ENV_VAR, "1")
+                .env("NO_COLOR", "1")
+                .env("CARGO_TERM_COLOR", "never");
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
+    taskline::crash::install_panic_hook("taskline", env!("CARGO_PKG_VERSION"));
     env_logger::init();
     
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let ui = Ui { ci: cli.ci || taskline::ci::detect() };
+    if let Some(name) = matches.subcommand_name() {
+        taskline::telemetry::record(|telemetry| telemetry.record_command(name));
+    }
 
     match cli.command {
-        Commands::Init { filename, version } => {
+        Commands::Init { filename, version, author, email, template, vars } => {
             // Route to taskline-init binary
-            let mut cmd = Command::new("taskline-init");
+            let mut cmd = component("taskline-init");
             cmd.arg(&filename);
             
             if let Some(ver) = version {
                 cmd.arg(&ver);
             }
+            if let Some(author) = author {
+                cmd.arg("--author").arg(author);
+            }
+            if let Some(email) = email {
+                cmd.arg("--email").arg(email);
+            }
+            if let Some(template) = template {
+                cmd.arg("--template").arg(template);
+            }
+            for var in vars {
+                cmd.arg("--var").arg(var);
+            }
             
-            execute_command(cmd, "taskline-init").await;
+            execute_command(cmd, "taskline-init", ui).await;
         }
-        Commands::Bump { filename, bump_type } => {
+        Commands::Bump { filename, bump_type, auto, changed_since } => {
             // Route to taskline-bump binary
-            let mut cmd = Command::new("taskline-bump");
-            cmd.arg(&filename);
+            let mut cmd = component("taskline-bump");
+            if let Some(filename) = &filename {
+                cmd.arg(filename);
+            }
+            if let Some(since) = &changed_since {
+                cmd.args(["--changed-since", since]);
+            }
             
             if let Some(bt) = bump_type {
                 match bt {
-                    BumpType::Major => cmd.arg("major"),
-                    BumpType::Minor => cmd.arg("minor"),
-                    BumpType::Patch => cmd.arg("patch"),
+                    BumpType::Major => cmd.args(["--fmt", "x.."]),
+                    BumpType::Minor => cmd.args(["--fmt", ".x."]),
+                    BumpType::Patch => cmd.args(["--fmt", "..x"]),
                 };
             }
+            if auto {
+                cmd.arg("--auto");
+            }
             
-            execute_command(cmd, "taskline-bump").await;
+            execute_command(cmd, "taskline-bump", ui).await;
+        }
+        Commands::Release { filename, bump_type, push, dry_run } => {
+            // Route to taskline-bump binary, which owns bumping and the git steps
+            let mut cmd = component("taskline-bump");
+            cmd.args([filename.as_str(), "--release"]);
+            match bump_type {
+                Some(BumpType::Major) => cmd.args(["--fmt", "x.."]),
+                Some(BumpType::Minor) => cmd.args(["--fmt", ".x."]),
+                Some(BumpType::Patch) => cmd.args(["--fmt", "..x"]),
+                None => cmd.arg("--auto"),
+            };
+            if push {
+                cmd.arg("--push");
+            }
+            if dry_run {
+                cmd.arg("--dry-run");
+            }
+            
+            execute_command(cmd, "taskline-bump", ui).await;
+        }
+        Commands::Publish { filename } => {
+            if let Err(e) = publish_script(&filename, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Pull { spec, locked, offline, deny_deprecated, ignore_expiry } => {
+            let checks = UsageChecks { deny_deprecated, ignore_expiry };
+            if let Err(e) = pull_script(&spec, PullOptions { locked, offline }, checks, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Encrypt { filename, recipients, passphrase } => {
+            if let Err(e) = encrypt_script(&filename, &recipients, passphrase, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Decrypt { filename, identity } => {
+            if let Err(e) = decrypt_script(&filename, identity, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Info { filename } => {
+            if let Err(e) = show_info(&filename, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Convert { filename, to } => {
+            if let Err(e) = convert_script(&filename, to, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Latest { pattern } => match taskline::versioned::latest(std::path::Path::new(&pattern)) {
+            Ok(Some(file)) => println!("{}", file.path.display()),
+            Ok(None) => fail(TasklineError::FileError(format!("No versioned file matches '{}'", pattern)), ui),
+            Err(e) => fail(e, ui),
+        },
+        Commands::Gc { pattern, keep, dry_run } => {
+            if let Err(e) = collect_garbage(&pattern, keep.get(), dry_run, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::ExportMeta { filename, format } => {
+            if let Err(e) = export_metadata(&filename, format, ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Package { formats, bin_dir, out_dir, url } => {
+            if let Err(e) = build_packages(&formats, bin_dir, &out_dir, url.as_deref(), ui) {
+                fail(e, ui);
+            }
+        }
+        Commands::Schema { validate } => match validate {
+            Some(file) => {
+                if let Err(e) = validate_metadata_file(&file, ui) {
+                    fail(e, ui);
+                }
+            }
+            None => print!("{}", taskline::schema::METADATA_SCHEMA),
+        },
+        Commands::Update { codename } => {
+            if let Err(e) = update_lockfile(codename.as_deref(), ui) {
+                fail(e, ui);
+            }
         }
         Commands::Install { force } => {
-            install_components(force).await;
+            install_components(force, ui).await;
         }
-        Commands::Doctor => {
-            check_installation().await;
+        Commands::Doctor { file } => {
+            check_installation(ui).await;
+            if let Some(file) = file {
+                if !check_requirements(&file, ui) {
+                    exit(1);
+                }
+            }
+        }
+        Commands::Validate { files, all, jobs } => {
+            match validate_scripts(files, all, jobs, ui) {
+                Ok(true) => {}
+                Ok(false) => exit(1),
+                Err(e) => fail(e, ui),
+            }
+        }
+        Commands::Telemetry { action } => {
+            if let Err(e) = manage_telemetry(action, ui) {
+                fail(e, ui);
+            }
         }
     }
 }
 
-async fn execute_command(mut cmd: Command, binary_name: &str) {
-    match cmd.status() {
+/// Report a failed command, counting its error category if telemetry is on, and exit
+fn fail(e: TasklineError, ui: Ui) -> ! {
+    taskline::telemetry::record(|telemetry| telemetry.record_error(&e));
+    eprintln!("{} {}", ui.mark("❌", "error:"), e);
+    exit(1)
+}
+
+/// A command for a Taskline component, resolved on PATH so Windows finds `.exe` and
+/// `.cmd` installs alike; unresolved names are left for spawning to report
+fn component(name: &str) -> Command {
+    match taskline::executable::find(name) {
+        Some(path) => Command::new(path),
+        None => Command::new(name),
+    }
+}
+
+async fn execute_command(mut cmd: Command, binary_name: &str, ui: Ui) {
+    ui.configure(&mut cmd);
+    
+    // Listen before spawning so an early Ctrl-C can't slip past us
+    let mut shutdown = ShutdownSignal::listen();
+    
+    let mut child = match AsyncCommand::from(cmd).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("{} Failed to execute {}: {}", ui.mark("❌", "error:"), binary_name, e);
+            eprintln!("{} Component not found. Run 'taskline install' to install all components", ui.mark("💡", "hint:"));
+            exit(1);
+        }
+    };
+    
+    let status = tokio::select! {
+        status = child.wait() => status,
+        signal = shutdown.recv() => {
+            // Forward the signal, give the component a grace period, then kill it
+            forward_signal(&child, signal);
+            if tokio::time::timeout(SHUTDOWN_GRACE, child.wait()).await.is_err() {
+                eprintln!("{} {} did not exit within {}s, killing it", ui.mark("⚠️ ", "warning:"), binary_name, SHUTDOWN_GRACE.as_secs());
+                let _ = child.kill().await;
+            }
+            eprintln!("{} {} interrupted", ui.mark("🛑", "interrupted:"), binary_name);
+            exit(128 + signal);
+        }
+    };
+    
+    match status {
         Ok(status) => {
             if !status.success() {
                 if let Some(code) = status.code() {
                     exit(code);
                 } else {
-                    eprintln!("❌ {} was terminated by signal", binary_name);
-                    exit(1);
+                    eprintln!("{} {} was terminated by signal", ui.mark("❌", "error:"), binary_name);
+                    exit(128 + terminating_signal(&status).unwrap_or(0));
                 }
             }
         }
         Err(e) => {
-            eprintln!("❌ Failed to execute {}: {}", binary_name, e);
-            eprintln!("💡 Component not found. Run 'taskline install' to install all components");
+            eprintln!("{} Failed to wait for {}: {}", ui.mark("❌", "error:"), binary_name, e);
             exit(1);
         }
     }
 }
 
-async fn install_components(force: bool) {
-    println!("🚀 Installing Taskline components...");
+/// SIGINT/SIGTERM (Ctrl-C/Ctrl-Break on Windows) listener for the dispatcher
+struct ShutdownSignal {
+    #[cfg(unix)]
+    interrupt: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+}
+
+impl ShutdownSignal {
+    fn listen() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            Self {
+                interrupt: signal(SignalKind::interrupt()).expect("failed to install SIGINT handler"),
+                terminate: signal(SignalKind::terminate()).expect("failed to install SIGTERM handler"),
+            }
+        }
+        #[cfg(not(unix))]
+        Self {}
+    }
+    
+    /// Wait for the next shutdown signal and return its number
+    async fn recv(&mut self) -> i32 {
+        #[cfg(unix)]
+        {
+            tokio::select! {
+                _ = self.interrupt.recv() => libc::SIGINT,
+                _ = self.terminate.recv() => libc::SIGTERM,
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = tokio::signal::ctrl_c().await;
+            2 // SIGINT, so the exit code matches Unix (130)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn forward_signal(child: &tokio::process::Child, signal: i32) {
+    if let Some(pid) = child.id() {
+        // SAFETY: plain kill(2) on a pid we spawned and have not reaped yet
+        unsafe {
+            libc::kill(pid as libc::pid_t, signal);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn forward_signal(_child: &tokio::process::Child, _signal: i32) {
+    // Console Ctrl-C is already delivered to every process attached to the console
+}
+
+#[cfg(unix)]
+fn terminating_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn terminating_signal(_status: &std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+async fn install_components(force: bool, ui: Ui) {
+    println!("{} Installing Taskline components...", ui.mark("🚀", "=>"));
     
     let components = vec![
         ("taskline-init", "Script initialization tool"),
@@ -121,10 +597,12 @@ async fn install_components(force: bool) {
     ];
     
     for (component, description) in components {
-        println!("📦 Installing {} ({})...", component, description);
+        println!("{} Installing {} ({})...", ui.mark("📦", "=>"), component, description);
         
-        let mut cmd = AsyncCommand::new("cargo");
-        cmd.args(&["install", component]);
+        let mut std_cmd = Command::new("cargo");
+        std_cmd.args(["install", component]);
+        ui.configure(&mut std_cmd);
+        let mut cmd = AsyncCommand::from(std_cmd);
         
         if force {
             cmd.arg("--force");
@@ -133,30 +611,31 @@ async fn install_components(force: bool) {
         match cmd.status().await {
             Ok(status) => {
                 if status.success() {
-                    println!("✅ {} installed successfully", component);
+                    println!("{} {} installed successfully", ui.mark("✅", "ok:"), component);
                 } else {
-                    eprintln!("❌ Failed to install {}", component);
+                    eprintln!("{} Failed to install {}", ui.mark("❌", "error:"), component);
                     exit(1);
                 }
             }
             Err(e) => {
-                eprintln!("❌ Error installing {}: {}", component, e);
+                eprintln!("{} Error installing {}: {}", ui.mark("❌", "error:"), component, e);
                 exit(1);
             }
         }
     }
     
-    println!("🎉 All Taskline components installed successfully!");
-    println!("");
+    println!("{} All Taskline components installed successfully!", ui.mark("🎉", "ok:"));
+    println!();
     println!("Available commands:");
     println!("  taskline init <filename> [version]  - Initialize a new script");
-    println!("  taskline bump <filename> [type]     - Bump script version");
+    println!("  taskline bump <filename> [type]     - Bump script version (--auto from commits)");
+    println!("  taskline release <filename> [type]  - Bump, changelog, commit and tag");
     println!("  taskline doctor                     - Check installation status");
 }
 
-async fn check_installation() {
-    println!("🔍 Checking Taskline installation...");
-    println!("");
+async fn check_installation(ui: Ui) {
+    println!("{} Checking Taskline installation...", ui.mark("🔍", "=>"));
+    println!();
     
     let components = vec![
         ("taskline", "Main CLI dispatcher"),
@@ -169,25 +648,500 @@ async fn check_installation() {
     for (component, description) in components {
         print!("  {} ({})... ", component, description);
         
-        let mut cmd = AsyncCommand::new("which");
-        cmd.arg(component);
-        
-        match cmd.status().await {
-            Ok(status) if status.success() => {
-                println!("✅ Installed");
+        match taskline::executable::find(component) {
+            Some(path) => {
+                println!("{}Installed ({})", ui.mark("✅ ", ""), path.display());
             }
-            _ => {
-                println!("❌ Not found");
+            None => {
+                println!("{}Not found", ui.mark("❌ ", ""));
                 all_installed = false;
             }
         }
     }
     
-    println!("");
+    println!();
     
     if all_installed {
-        println!("🎉 All Taskline components are properly installed!");
+        println!("{} All Taskline components are properly installed!", ui.mark("🎉", "ok:"));
+    } else {
+        println!("{} Some components are missing. Run 'taskline install' to install them.", ui.mark("⚠️ ", "warning:"));
+        if ui.ci {
+            exit(1);
+        }
+    }
+}
+
+/// Verify the tools declared by a script's `@Taskline requires` lines
+fn check_requirements(file: &str, ui: Ui) -> bool {
+    use taskline::requirements::RequirementStatus;
+    
+    println!();
+    println!("{} Checking tool requirements for {}...", ui.mark("🔍", "=>"), file);
+    println!();
+    
+    let content = match taskline::encryption::read_script(std::path::Path::new(file), !ui.ci) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("{} Failed to read {}: {}", ui.mark("❌", "error:"), file, e);
+            return false;
+        }
+    };
+    let metadata = taskline::TasklineMetadata::parse(&content);
+    warn_deprecated(file, &metadata, ui);
+    if metadata.is_expired(&today()) {
+        eprintln!("{} {} expired on {}", ui.mark("⚠️ ", "warning:"), file, metadata.expires.as_deref().unwrap_or_default());
+    }
+    if let Err(e) = check_naming(std::path::Path::new(file), &metadata.codename) {
+        eprintln!("{} {}", ui.mark("❌", "error:"), e);
+        return false;
+    }
+    if let Err(e) = metadata.check_taskline_version(&cli_version()) {
+        eprintln!("{} {}", ui.mark("❌", "error:"), e);
+        return false;
+    }
+    if let Some(Err(e)) = metadata.license.as_deref().map(taskline::license::validate_license) {
+        eprintln!("{} {}", ui.mark("❌", "error:"), e);
+        return false;
+    }
+    let requirements = match metadata.requirements() {
+        Ok(requirements) => requirements,
+        Err(e) => {
+            eprintln!("{} {}", ui.mark("❌", "error:"), e);
+            return false;
+        }
+    };
+    
+    if requirements.is_empty() {
+        println!("  No tools required");
+        return true;
+    }
+    
+    let script_dir = match std::path::Path::new(file).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => std::path::Path::new("."),
+    };
+    let pins = match taskline::tool_versions::discover(script_dir) {
+        Ok(pins) => pins,
+        Err(e) => {
+            eprintln!("{} {}", ui.mark("❌", "error:"), e);
+            return false;
+        }
+    };
+    
+    // Tools installed into the script's declared directories count, e.g. `./node_modules/.bin`
+    let path = match metadata.augmented_path(script_dir, std::env::var_os("PATH").as_deref()) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("{} {}", ui.mark("❌", "error:"), e);
+            return false;
+        }
+    };
+    
+    let mut all_satisfied = true;
+    for requirement in &requirements {
+        print!("  {}... ", requirement);
+        match requirement.check_on(&path) {
+            RequirementStatus::Satisfied(Some(version)) => println!("{}Found {}", ui.mark("✅ ", ""), version),
+            RequirementStatus::Satisfied(None) => println!("{}Found", ui.mark("✅ ", "")),
+            RequirementStatus::Missing => {
+                println!("{}Not found", ui.mark("❌ ", ""));
+                all_satisfied = false;
+            }
+            RequirementStatus::Mismatch(version) => {
+                println!("{}Found {}, which does not match", ui.mark("❌ ", ""), version);
+                all_satisfied = false;
+            }
+            RequirementStatus::UnknownVersion => {
+                println!("{}Found, but its version could not be determined", ui.mark("❌ ", ""));
+                all_satisfied = false;
+            }
+        }
+        // A version manager pin that contradicts the header breaks the script for everyone using it
+        if let Some(pin) = pins.get(&requirement.tool) {
+            let allowed = |req: &taskline::VersionReq| pin.range.as_ref().map_or(true, |range| req.intersects(range));
+            if !requirement.req.as_ref().map_or(true, allowed) {
+                println!("    {}pinned to {} in {}, which does not match", ui.mark("❌ ", ""), pin.spec, pin.source.display());
+                all_satisfied = false;
+            }
+        }
+    }
+    
+    println!();
+    if all_satisfied {
+        println!("{} All required tools are available!", ui.mark("🎉", "ok:"));
+    } else {
+        println!("{} Some required tools are missing or outdated.", ui.mark("⚠️ ", "warning:"));
+    }
+    all_satisfied
+}
+
+fn show_info(filename: &str, ui: Ui) -> Result<(), TasklineError> {
+    let content = taskline::encryption::read_script(std::path::Path::new(filename), !ui.ci)?;
+    let metadata = taskline::TasklineMetadata::validate(&content)?;
+    
+    match metadata.version {
+        Some(version) => println!("{} {} {}", ui.mark("📄", "=>"), metadata.codename, version),
+        None => println!("{} {}", ui.mark("📄", "=>"), metadata.codename),
+    }
+    let field = |label: &str, value: String| println!("  {:<12}{}", label, value);
+    for (i, author) in metadata.authors.iter().enumerate() {
+        field(if i == 0 { "Authors:" } else { "" }, author.to_string());
+    }
+    if let Some(created) = &metadata.created {
+        field("Created:", created.clone());
+    }
+    if let Some(expires) = &metadata.expires {
+        let expired = if metadata.is_expired(&today()) { " (expired)" } else { "" };
+        field("Expires:", format!("{}{}", expires, expired));
+    }
+    if let Some(license) = &metadata.license {
+        field("License:", license.clone());
+    }
+    if let Some(message) = &metadata.deprecated {
+        field("Deprecated:", if message.is_empty() { "yes".to_string() } else { message.clone() });
+    }
+    if !metadata.path.is_empty() {
+        field("Path:", metadata.path.join(", "));
+    }
+    for (i, requires) in metadata.requires.iter().enumerate() {
+        field(if i == 0 { "Requires:" } else { "" }, requires.clone());
+    }
+    if let Some(req) = &metadata.requires_taskline {
+        let outdated = if metadata.check_taskline_version(&cli_version()).is_err() { " (this CLI is too old)" } else { "" };
+        field("Taskline:", format!("{}{}", req, outdated));
+    }
+    Ok(())
+}
+
+/// Print a prominent warning when a script is marked `@Taskline deprecated`
+fn warn_deprecated(name: &str, metadata: &taskline::TasklineMetadata, ui: Ui) {
+    if let Some(message) = &metadata.deprecated {
+        eprintln!();
+        eprintln!("{} {} IS DEPRECATED", ui.mark("⚠️ ", "warning:"), name);
+        if !message.is_empty() {
+            eprintln!("   {}", message);
+        }
+        eprintln!();
+    }
+}
+
+/// Today's local date as `YYYY-MM-DD`, for `@Taskline expires`
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Validate the given scripts, or the whole workspace with `all`; `Ok(false)` when any is invalid
+fn validate_scripts(files: Vec<String>, all: bool, jobs: Option<usize>, ui: Ui) -> Result<bool, TasklineError> {
+    use taskline::workspace;
+    
+    let cwd = std::env::current_dir().map_err(|e| TasklineError::FileError(e.to_string()))?;
+    let loaded = TasklineConfig::discover(&cwd)?;
+    let paths = if all {
+        workspace::find_scripts(loaded.as_ref().map_or(&cwd, |loaded| &loaded.root))?
     } else {
-        println!("⚠️  Some components are missing. Run 'taskline install' to install them.");
+        files.into_iter().map(std::path::PathBuf::from).collect()
+    };
+    let naming = loaded.as_ref().map(|loaded| &loaded.config.naming);
+    let diagnostics = workspace::validate_scripts(&paths, naming, jobs.unwrap_or_else(workspace::default_jobs));
+    
+    for diagnostic in &diagnostics {
+        let path = diagnostic.path.strip_prefix(&cwd).unwrap_or(&diagnostic.path);
+        eprintln!("{} {}: {}", ui.mark("❌", "error:"), path.display(), diagnostic.error);
     }
-}
\ No newline at end of file
+    if diagnostics.is_empty() {
+        println!("{} {} script(s) valid", ui.mark("✅", "ok:"), paths.len());
+    } else {
+        println!("{} {} of {} script(s) invalid", ui.mark("⚠️ ", "warning:"), diagnostics.len(), paths.len());
+    }
+    Ok(diagnostics.is_empty())
+}
+
+fn manage_telemetry(action: TelemetryAction, ui: Ui) -> Result<(), TasklineError> {
+    use taskline::telemetry::{self, Telemetry};
+    
+    let path = Telemetry::default_path()
+        .ok_or_else(|| TasklineError::FileError(format!("Could not determine a config directory; set {}", telemetry::TELEMETRY_FILE_ENV_VAR)))?;
+    let mut state = Telemetry::load(&path)?;
+    match action {
+        TelemetryAction::On | TelemetryAction::Off => {
+            let enabled = matches!(action, TelemetryAction::On);
+            state.set_enabled(enabled);
+            state.save(&path)?;
+            if enabled {
+                println!("{} Telemetry enabled: command counts and error categories are recorded in {}", ui.mark("📊", "ok:"), path.display());
+                println!("   Arguments, paths and error messages are never recorded, and nothing is sent anywhere.");
+                println!("   Share the counts with 'taskline telemetry export'; disable with 'taskline telemetry off'.");
+            } else {
+                println!("{} Telemetry disabled and collected data deleted", ui.mark("📊", "ok:"));
+            }
+        }
+        TelemetryAction::Status => {
+            let state_label = match (state.enabled, telemetry::do_not_track()) {
+                (true, true) => "enabled, but paused by DO_NOT_TRACK",
+                (true, false) => "enabled",
+                (false, _) => "disabled",
+            };
+            println!("{} Telemetry is {} ({})", ui.mark("📊", "=>"), state_label, path.display());
+            for (label, counts) in [("Commands:", &state.commands), ("Errors:", &state.errors)] {
+                for (i, (name, count)) in counts.iter().enumerate() {
+                    println!("  {:<12}{} x{}", if i == 0 { label } else { "" }, name, count);
+                }
+            }
+            if state.enabled {
+                println!("Nothing is transmitted; 'taskline telemetry export' prints these counts for sharing.");
+            }
+        }
+        TelemetryAction::Export => {
+            let report = state.report(env!("CARGO_PKG_VERSION"))?;
+            let json = serde_json::to_string_pretty(&report)
+                .map_err(|e| TasklineError::ConfigError(format!("Cannot serialize telemetry: {}", e)))?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Version of this CLI, checked against `@Taskline requires-taskline`
+fn cli_version() -> Version {
+    Version::parse(env!("CARGO_PKG_VERSION")).expect("package version is X.Y.Z")
+}
+
+/// Header checks applied before a script is used
+#[derive(Clone, Copy)]
+struct UsageChecks {
+    deny_deprecated: bool,
+    ignore_expiry: bool,
+}
+
+impl UsageChecks {
+    /// Refuse a script needing a newer Taskline or an expired one (unless `ignore_expiry`),
+    /// and warn about a deprecated one, or refuse it when `deny_deprecated` is set
+    fn apply(self, name: &str, content: &str, ui: Ui) -> Result<(), TasklineError> {
+        let metadata = taskline::TasklineMetadata::parse(content);
+        metadata.check_taskline_version(&cli_version())
+            .map_err(|e| TasklineError::VersionError(format!("{}: {}", name, e)))?;
+        if metadata.is_expired(&today()) && !self.ignore_expiry {
+            return Err(TasklineError::ParseError(format!(
+                "{} expired on {}; pass --ignore-expiry to use it anyway",
+                name, metadata.expires.as_deref().unwrap_or_default()
+            )));
+        }
+        match &metadata.deprecated {
+            Some(message) if self.deny_deprecated => Err(TasklineError::ParseError(match message.is_empty() {
+                true => format!("{} is deprecated (--deny-deprecated)", name),
+                false => format!("{} is deprecated: {} (--deny-deprecated)", name, message),
+            })),
+            _ => {
+                warn_deprecated(name, &metadata, ui);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn collect_garbage(pattern: &str, keep: usize, dry_run: bool, ui: Ui) -> Result<(), TasklineError> {
+    use taskline::versioned;
+    
+    let files = versioned::find(std::path::Path::new(pattern))?;
+    let outdated = versioned::outdated(&files, keep);
+    for file in &outdated {
+        if dry_run {
+            println!("{} Would delete {}", ui.mark("🗑️ ", "=>"), file.path.display());
+        } else {
+            std::fs::remove_file(&file.path)
+                .map_err(|e| TasklineError::FileError(format!("{}: {}", file.path.display(), e)))?;
+            println!("{} Deleted {}", ui.mark("🗑️ ", "=>"), file.path.display());
+        }
+    }
+    let verb = if dry_run { "would be deleted" } else { "deleted" };
+    println!("{} {} of {} versioned file(s) {}, keeping the newest {} of each script", ui.mark("✅", "ok:"), outdated.len(), files.len(), verb, keep);
+    Ok(())
+}
+
+fn export_metadata(filename: &str, format: MetaFormat, ui: Ui) -> Result<(), TasklineError> {
+    let content = taskline::encryption::read_script(std::path::Path::new(filename), !ui.ci)?;
+    let metadata = taskline::TasklineMetadata::validate(&content)?;
+    let exported = match format {
+        MetaFormat::Json => serde_json::to_string_pretty(&metadata).map(|json| json + "\n").map_err(|e| e.to_string()),
+        MetaFormat::Toml => toml::to_string(&metadata).map_err(|e| e.to_string()),
+        MetaFormat::Yaml => serde_yaml::to_string(&metadata).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| TasklineError::ParseError(format!("Cannot export metadata of {}: {}", filename, e)))?;
+    print!("{}", exported);
+    Ok(())
+}
+
+fn build_packages(formats: &[PackageFormat], bin_dir: std::path::PathBuf, out_dir: &std::path::Path, url: Option<&str>, ui: Ui) -> Result<(), TasklineError> {
+    use clap_complete::Shell;
+    use taskline::package::{self, Completions, Payload};
+    
+    let completion = |shell: Shell| {
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Cli::command(), "taskline", &mut script);
+        script
+    };
+    let payload = Payload {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        bin_dir,
+        completions: Completions { bash: completion(Shell::Bash), zsh: completion(Shell::Zsh), fish: completion(Shell::Fish) },
+    };
+    for format in formats {
+        let format = match format {
+            PackageFormat::Deb => package::PackageFormat::Deb,
+            PackageFormat::Rpm => package::PackageFormat::Rpm,
+            PackageFormat::Homebrew => package::PackageFormat::Homebrew,
+        };
+        for file in package::build(format, &payload, out_dir, url)? {
+            println!("{} Wrote {}", ui.mark("📦", "ok:"), file.display());
+        }
+    }
+    Ok(())
+}
+
+fn convert_script(filename: &str, to: HeaderFormat, ui: Ui) -> Result<(), TasklineError> {
+    use taskline::frontmatter;
+    
+    let path = std::path::Path::new(filename);
+    let bytes = std::fs::read(path).map_err(|e| TasklineError::FileError(format!("{}: {}", filename, e)))?;
+    if taskline::encryption::is_encrypted(&bytes) {
+        return Err(TasklineError::FileError(format!("{} is encrypted; decrypt it first", filename)));
+    }
+    let content = String::from_utf8(bytes)
+        .map_err(|_| TasklineError::FileError(format!("{}: not valid UTF-8", filename)))?;
+    let (converted, label) = match to {
+        HeaderFormat::Frontmatter => (frontmatter::header_to_frontmatter(&content)?, "YAML frontmatter"),
+        HeaderFormat::Header => (frontmatter::frontmatter_to_header(&content)?, "@Taskline lines"),
+    };
+    std::fs::write(path, converted)
+        .map_err(|e| TasklineError::FileError(format!("{}: {}", filename, e)))?;
+    println!("{} Converted {} to {}", ui.mark("🔁", "ok:"), filename, label);
+    Ok(())
+}
+
+fn validate_metadata_file(file: &str, ui: Ui) -> Result<(), TasklineError> {
+    let json = if file == "-" {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        std::fs::read_to_string(file)
+    }
+    .map_err(|e| TasklineError::FileError(format!("{}: {}", file, e)))?;
+    taskline::schema::validate_metadata_json(&json)?;
+    println!("{} {} matches the metadata schema", ui.mark("✅", "ok:"), file);
+    Ok(())
+}
+
+/// Apply the `[naming]` policy of the nearest `.taskline.toml` to a script's codename
+fn check_naming(script: &std::path::Path, codename: &str) -> Result<(), TasklineError> {
+    let dir = match script.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => std::path::Path::new("."),
+    };
+    let dir = std::fs::canonicalize(dir)
+        .map_err(|e| TasklineError::FileError(format!("{}: {}", dir.display(), e)))?;
+    match TasklineConfig::discover(&dir)? {
+        Some(loaded) => loaded.config.naming.check_codename(codename),
+        None => Ok(()),
+    }
+}
+
+fn publish_script(filename: &str, ui: Ui) -> Result<(), TasklineError> {
+    let path = std::path::Path::new(filename);
+    let content = taskline::encryption::read_script(path, !ui.ci)?;
+    let script_dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => std::path::Path::new("."),
+    };
+    
+    let (registry, _) = Registry::discover(script_dir)?;
+    let published = registry.open()?.publish(&content)?;
+    println!("{} Published {} {}", ui.mark("📦", "ok:"), published.codename, published.version);
+    Ok(())
+}
+
+/// `<codename>[.<version>].<extension>`, the naming convention for scripts derived from their header
+fn script_file_name(content: &str, extension: &str) -> Result<String, TasklineError> {
+    let metadata = taskline::TasklineMetadata::parse(content);
+    let codename = Codename::parse(&metadata.codename)?;
+    Ok(match metadata.version {
+        Some(version) => format!("{}.{}.{}", codename, version, extension),
+        None => format!("{}.{}", codename, extension),
+    })
+}
+
+fn encrypt_script(filename: &str, recipients: &[String], passphrase: bool, ui: Ui) -> Result<(), TasklineError> {
+    use taskline::encryption::{self, PASSPHRASE_ENV_VAR};
+    
+    let path = std::path::Path::new(filename);
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| TasklineError::FileError(format!("{}: {}", filename, e)))?;
+    if encryption::is_encrypted(content.as_bytes()) {
+        return Err(TasklineError::EncryptionError(format!("{} is already encrypted", filename)));
+    }
+    
+    let encrypted = if passphrase {
+        let passphrase = match std::env::var(PASSPHRASE_ENV_VAR) {
+            Ok(passphrase) => passphrase,
+            Err(_) if ui.ci => return Err(TasklineError::EncryptionError(format!("Set {} to encrypt in CI", PASSPHRASE_ENV_VAR))),
+            Err(_) => {
+                let read = |prompt: &str| rpassword::prompt_password(prompt)
+                    .map_err(|e| TasklineError::EncryptionError(format!("Failed to read passphrase: {}", e)));
+                let first = read("Passphrase: ")?;
+                if first.is_empty() || first != read("Confirm passphrase: ")? {
+                    return Err(TasklineError::EncryptionError("Passphrases are empty or do not match".to_string()));
+                }
+                first
+            }
+        };
+        encryption::encrypt_with_passphrase(&content, &passphrase)?
+    } else {
+        encryption::encrypt(&content, recipients)?
+    };
+    
+    let target = path.with_file_name(script_file_name(&content, taskline::constants::ENCRYPTED_EXTENSION)?);
+    write_new_file(&target, &encrypted, "Encryption")?;
+    println!("{} Encrypted {} into {}", ui.mark("🔒", "ok:"), filename, target.display());
+    Ok(())
+}
+
+fn decrypt_script(filename: &str, identity: Option<std::path::PathBuf>, ui: Ui) -> Result<(), TasklineError> {
+    use taskline::encryption::{self, DecryptionKey};
+    
+    let path = std::path::Path::new(filename);
+    let bytes = std::fs::read(path)
+        .map_err(|e| TasklineError::FileError(format!("{}: {}", filename, e)))?;
+    if !encryption::is_encrypted(&bytes) {
+        return Err(TasklineError::EncryptionError(format!("{} is not encrypted", filename)));
+    }
+    let key = match identity {
+        Some(identity) => DecryptionKey::IdentityFile(identity),
+        None => encryption::key_from_env_or_prompt(path, encryption::is_passphrase_encrypted(&bytes)?, !ui.ci)?,
+    };
+    let content = encryption::decrypt(&bytes, &key)?;
+    
+    let target = path.with_file_name(script_file_name(&content, taskline::constants::TASKLINE_EXTENSION)?);
+    write_new_file(&target, content.as_bytes(), "Decryption")?;
+    println!("{} Decrypted {} into {}", ui.mark("🔓", "ok:"), filename, target.display());
+    Ok(())
+}
+
+fn pull_script(spec: &str, options: PullOptions, checks: UsageChecks, ui: Ui) -> Result<(), TasklineError> {
+    let spec = PullSpec::parse(spec)?;
+    let source = PullSource::discover(std::path::Path::new("."))?;
+    let pulled = source.pull(&spec, options, std::path::Path::new("."), |name, content| checks.apply(name, content, ui))?;
+    if let Some(e) = pulled.cache_error {
+        eprintln!("{} Could not cache {} {}: {}", ui.mark("⚠️ ", "warning:"), pulled.codename, pulled.version, e);
+    }
+    println!("{} Pulled {} {} into {}", ui.mark("📥", "ok:"), pulled.codename, pulled.version, pulled.path.display());
+    Ok(())
+}
+
+fn update_lockfile(only: Option<&str>, ui: Ui) -> Result<(), TasklineError> {
+    let updates = PullSource::discover(std::path::Path::new("."))?.update(only)?;
+    for update in &updates {
+        println!("{} Updating {} {} -> {}", ui.mark("🔄", "=>"), update.codename, update.from, update.to);
+    }
+    if updates.is_empty() {
+        println!("{} {} is up to date", ui.mark("✅", "ok:"), LOCKFILE_NAME);
+    }
+    Ok(())
+}
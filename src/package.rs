@@ -0,0 +1,308 @@
+// File: src/package.rs
+// --- `taskline package`: .deb, .rpm and a Homebrew formula from already built binaries
+// --- The archives are built by dpkg-deb, rpmbuild and tar; this module lays out their inputs
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sha2::{Digest, Sha256};
+use crate::TasklineError;
+
+/// Binaries shipped in every package: the dispatcher and the components it runs
+pub const BINARIES: [&str; 3] = ["taskline", "taskline-init", "taskline-bump"];
+
+/// A distributable package kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageFormat {
+    /// Debian package, built with `dpkg-deb`
+    Deb,
+    /// RPM package, built with `rpmbuild`
+    Rpm,
+    /// Homebrew formula plus the tarball it installs
+    Homebrew,
+}
+
+/// Completion scripts for the `taskline` dispatcher
+pub struct Completions {
+    /// bash
+    pub bash: Vec<u8>,
+    /// zsh (`_taskline`)
+    pub zsh: Vec<u8>,
+    /// fish
+    pub fish: Vec<u8>,
+}
+
+/// What every package contains
+pub struct Payload {
+    /// Version of the packages, without a leading `v`
+    pub version: String,
+    /// Directory holding the built [`BINARIES`], e.g. `target/release`
+    pub bin_dir: PathBuf,
+    /// Completions installed next to the binaries
+    pub completions: Completions,
+}
+
+/// Build one package into `out_dir` and return the files written. Homebrew needs `url`,
+/// the address the tarball will be published under (the directory, without a file name).
+pub fn build(format: PackageFormat, payload: &Payload, out_dir: &Path, url: Option<&str>) -> Result<Vec<PathBuf>, TasklineError> {
+    let binaries = find_binaries(&payload.bin_dir)?;
+    std::fs::create_dir_all(out_dir).map_err(|e| io(out_dir, e))?;
+    let staging = tempfile::TempDir::new().map_err(|e| TasklineError::FileError(format!("Failed to create staging directory: {}", e)))?;
+    let arch = std::env::consts::ARCH;
+
+    match format {
+        PackageFormat::Deb => {
+            let root = staging.path().join("root");
+            stage(&root, &binaries, &payload.completions, "usr/share/zsh/vendor-completions")?;
+            write(&root.join("DEBIAN/control"), deb_control(&payload.version, deb_arch(arch)).as_bytes())?;
+            let package = out_dir.join(format!("taskline_{}_{}.deb", package_version(&payload.version), deb_arch(arch)));
+            let mut cmd = Command::new("dpkg-deb");
+            cmd.args(["--root-owner-group", "--build"]).arg(&root).arg(&package);
+            run(&mut cmd, "dpkg-deb")?;
+            Ok(vec![package])
+        }
+        PackageFormat::Rpm => {
+            let root = staging.path().join("root");
+            stage(&root, &binaries, &payload.completions, "usr/share/zsh/site-functions")?;
+            let spec = staging.path().join("taskline.spec");
+            write(&spec, rpm_spec(&payload.version, &root).as_bytes())?;
+            let name = format!("taskline-{}-1.{}.rpm", package_version(&payload.version), rpm_arch(arch));
+            let mut cmd = Command::new("rpmbuild");
+            cmd.args(["-bb", "--quiet", "--target", rpm_arch(arch)])
+                .arg("--define").arg(format!("_topdir {}", staging.path().join("rpmbuild").display()))
+                .arg("--define").arg(format!("_rpmdir {}", absolute(out_dir)?.display()))
+                .arg("--define").arg("_build_name_fmt %%{NAME}-%%{VERSION}-%%{RELEASE}.%%{ARCH}.rpm")
+                .arg(&spec);
+            run(&mut cmd, "rpmbuild")?;
+            Ok(vec![out_dir.join(name)])
+        }
+        PackageFormat::Homebrew => {
+            let url = url.ok_or_else(|| TasklineError::ConfigError("A Homebrew formula needs --url, where the tarball will be published".to_string()))?;
+            let top = format!("taskline-{}", payload.version);
+            let dir = staging.path().join(&top);
+            for binary in &binaries {
+                copy_executable(binary, &dir.join(binary.file_name().unwrap_or_default()))?;
+            }
+            write(&dir.join("completions/taskline.bash"), &payload.completions.bash)?;
+            write(&dir.join("completions/_taskline"), &payload.completions.zsh)?;
+            write(&dir.join("completions/taskline.fish"), &payload.completions.fish)?;
+
+            let tarball_name = format!("taskline-{}-{}-{}.tar.gz", payload.version, std::env::consts::OS, arch);
+            let tarball = out_dir.join(&tarball_name);
+            let mut cmd = Command::new("tar");
+            cmd.arg("-czf").arg(absolute(out_dir)?.join(&tarball_name)).arg("-C").arg(staging.path()).arg(&top);
+            run(&mut cmd, "tar")?;
+
+            let bytes = std::fs::read(&tarball).map_err(|e| io(&tarball, e))?;
+            let tarball_url = format!("{}/{}", url.trim_end_matches('/'), tarball_name);
+            let formula = out_dir.join("taskline.rb");
+            write(&formula, homebrew_formula(&payload.version, &tarball_url, &sha256_hex(&bytes)).as_bytes())?;
+            Ok(vec![tarball, formula])
+        }
+    }
+}
+
+/// The [`BINARIES`] in `bin_dir`, with the platform's executable suffix
+fn find_binaries(bin_dir: &Path) -> Result<Vec<PathBuf>, TasklineError> {
+    let paths: Vec<PathBuf> = BINARIES.iter().map(|name| bin_dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX))).collect();
+    let missing: Vec<String> = paths.iter().filter(|path| !path.is_file()).map(|path| path.display().to_string()).collect();
+    if !missing.is_empty() {
+        return Err(TasklineError::FileError(format!(
+            "Missing built binaries: {} (run 'cargo build --release --workspace' first)",
+            missing.join(", ")
+        )));
+    }
+    Ok(paths)
+}
+
+/// The filesystem tree shared by the deb and rpm packages, rooted at `root`
+fn stage(root: &Path, binaries: &[PathBuf], completions: &Completions, zsh_dir: &str) -> Result<(), TasklineError> {
+    for binary in binaries {
+        copy_executable(binary, &root.join("usr/bin").join(binary.file_name().unwrap_or_default()))?;
+    }
+    write(&root.join("usr/share/bash-completion/completions/taskline"), &completions.bash)?;
+    write(&root.join(zsh_dir).join("_taskline"), &completions.zsh)?;
+    write(&root.join("usr/share/fish/vendor_completions.d/taskline.fish"), &completions.fish)
+}
+
+/// Debian `control` file
+fn deb_control(version: &str, arch: &str) -> String {
+    format!(
+        "Package: taskline\nVersion: {}\nArchitecture: {}\nMaintainer: {}\nSection: devel\nPriority: optional\nHomepage: {}\nDescription: {}\n",
+        package_version(version),
+        arch,
+        env!("CARGO_PKG_AUTHORS").split(':').next().unwrap_or_default(),
+        env!("CARGO_PKG_HOMEPAGE"),
+        env!("CARGO_PKG_DESCRIPTION"),
+    )
+}
+
+/// RPM spec that installs the tree staged at `root` as is
+fn rpm_spec(version: &str, root: &Path) -> String {
+    let files: Vec<String> = BINARIES.iter().map(|name| format!("/usr/bin/{}", name)).chain([
+        "/usr/share/bash-completion/completions/taskline".to_string(),
+        "/usr/share/zsh/site-functions/_taskline".to_string(),
+        "/usr/share/fish/vendor_completions.d/taskline.fish".to_string(),
+    ]).collect();
+    format!(
+        "Name: taskline\nVersion: {}\nRelease: 1\nSummary: {}\nLicense: {}\nURL: {}\n\
+         %global debug_package %{{nil}}\n%global __strip /bin/true\n\n\
+         %description\n{}\n\n%install\nmkdir -p %{{buildroot}}\ncp -a {}/. %{{buildroot}}/\n\n%files\n{}\n",
+        package_version(version),
+        env!("CARGO_PKG_DESCRIPTION"),
+        env!("CARGO_PKG_LICENSE"),
+        env!("CARGO_PKG_HOMEPAGE"),
+        env!("CARGO_PKG_DESCRIPTION"),
+        root.display(),
+        files.join("\n"),
+    )
+}
+
+/// Homebrew formula installing the tarball at `url`
+fn homebrew_formula(version: &str, url: &str, sha256: &str) -> String {
+    let licenses: Vec<String> = env!("CARGO_PKG_LICENSE").split(" OR ").map(|license| format!("\"{}\"", license.trim())).collect();
+    let license = match licenses.as_slice() {
+        [single] => single.clone(),
+        _ => format!("any_of: [{}]", licenses.join(", ")),
+    };
+    let binaries: Vec<String> = BINARIES.iter().map(|name| format!("\"{}\"", name)).collect();
+    format!(
+        r##"class Taskline < Formula
+  desc "{}"
+  homepage "{}"
+  url "{}"
+  version "{}"
+  sha256 "{}"
+  license {}
+
+  def install
+    bin.install {}
+    bash_completion.install "completions/taskline.bash" => "taskline"
+    zsh_completion.install "completions/_taskline"
+    fish_completion.install "completions/taskline.fish"
+  end
+
+  test do
+    assert_match version.to_s, shell_output("#{{bin}}/taskline --version")
+  end
+end
+"##,
+        env!("CARGO_PKG_DESCRIPTION"),
+        env!("CARGO_PKG_HOMEPAGE"),
+        url,
+        version,
+        sha256,
+        license,
+        binaries.join(", "),
+    )
+}
+
+/// Debian and RPM sort `~` before anything, which is what a semver pre-release means
+fn package_version(version: &str) -> String {
+    version.replacen('-', "~", 1)
+}
+
+/// Debian's name for a Rust target architecture
+fn deb_arch(arch: &str) -> &str {
+    match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" => "i386",
+        "arm" => "armhf",
+        "powerpc64" => "ppc64el",
+        other => other,
+    }
+}
+
+/// RPM's name for a Rust target architecture
+fn rpm_arch(arch: &str) -> &str {
+    match arch {
+        "x86" => "i686",
+        "arm" => "armv7hl",
+        "powerpc64" => "ppc64le",
+        other => other,
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Run a packaging tool, failing with a hint when it is not installed
+fn run(cmd: &mut Command, tool: &str) -> Result<(), TasklineError> {
+    if crate::executable::find(tool).is_none() {
+        return Err(TasklineError::FileError(format!("{} was not found on PATH; it is needed for this package format", tool)));
+    }
+    let output = cmd.output().map_err(|e| TasklineError::FileError(format!("Failed to run {}: {}", tool, e)))?;
+    if !output.status.success() {
+        return Err(TasklineError::FileError(format!("{} failed: {}", tool, String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    Ok(())
+}
+
+fn write(path: &Path, content: &[u8]) -> Result<(), TasklineError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| io(dir, e))?;
+    }
+    std::fs::write(path, content).map_err(|e| io(path, e))
+}
+
+fn copy_executable(from: &Path, to: &Path) -> Result<(), TasklineError> {
+    if let Some(dir) = to.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| io(dir, e))?;
+    }
+    std::fs::copy(from, to).map_err(|e| io(from, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(to, std::fs::Permissions::from_mode(0o755)).map_err(|e| io(to, e))?;
+    }
+    Ok(())
+}
+
+/// Tools run from the staging directory need `out_dir` as an absolute path
+fn absolute(path: &Path) -> Result<PathBuf, TasklineError> {
+    std::fs::canonicalize(path).map_err(|e| io(path, e))
+}
+
+fn io(path: &Path, e: std::io::Error) -> TasklineError {
+    TasklineError::FileError(format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_and_arches() {
+        assert_eq!(package_version("1.2.0"), "1.2.0");
+        assert_eq!(package_version("1.2.0-beta.1"), "1.2.0~beta.1");
+        assert_eq!((deb_arch("x86_64"), rpm_arch("x86_64")), ("amd64", "x86_64"));
+        assert_eq!((deb_arch("aarch64"), rpm_arch("aarch64")), ("arm64", "aarch64"));
+    }
+
+    #[test]
+    fn test_package_metadata() {
+        let control = deb_control("0.2.0", "amd64");
+        assert!(control.starts_with("Package: taskline\nVersion: 0.2.0\nArchitecture: amd64\n"));
+        assert!(control.ends_with(&format!("Description: {}\n", env!("CARGO_PKG_DESCRIPTION"))));
+
+        let spec = rpm_spec("0.2.0", Path::new("/tmp/root"));
+        assert!(spec.contains("\nVersion: 0.2.0\n"));
+        assert!(spec.contains("cp -a /tmp/root/. %{buildroot}/"));
+        assert!(spec.contains("\n/usr/bin/taskline-bump\n"));
+
+        let formula = homebrew_formula("0.2.0", "https://example.com/taskline-0.2.0.tar.gz", "ab12");
+        assert!(formula.contains("  url \"https://example.com/taskline-0.2.0.tar.gz\"\n  version \"0.2.0\"\n  sha256 \"ab12\"\n"));
+        assert!(formula.contains("license any_of: [\"MIT\", \"Apache-2.0\"]"));
+        assert!(formula.contains("bin.install \"taskline\", \"taskline-init\", \"taskline-bump\""));
+        assert!(formula.contains("shell_output(\"#{bin}/taskline --version\")"));
+    }
+
+    #[test]
+    fn test_missing_binaries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(format!("taskline{}", std::env::consts::EXE_SUFFIX)), "").unwrap();
+        let error = find_binaries(dir.path()).unwrap_err().to_string();
+        assert!(error.contains("taskline-init") && error.contains("taskline-bump"));
+    }
+}
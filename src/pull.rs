@@ -0,0 +1,351 @@
+// File: src/pull.rs
+// --- `taskline pull` and `taskline update`: registry, lockfile and cache combined
+// --- Paths are explicit so the whole flow runs against a directory registry in tests
+
+use std::path::{Path, PathBuf};
+use crate::cache::ScriptCache;
+use crate::constants::TASKLINE_EXTENSION;
+use crate::lockfile::{Lockfile, LOCKFILE_NAME};
+use crate::registry::Registry;
+use crate::versioned;
+use crate::workspace::write_new_file;
+use crate::{Codename, TasklineError, Version, VersionReq};
+
+/// What to pull: `codename`, `codename@[v]X.Y.Z` or `codename@<constraint>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullSpec {
+    pub codename: String,
+    /// `None` when no version was given; a full `X.Y.Z`, with or without the `v`,
+    /// becomes `=X.Y.Z`
+    pub requirement: Option<VersionReq>,
+}
+
+impl PullSpec {
+    /// Parse a spec as typed on the command line
+    pub fn parse(spec: &str) -> Result<Self, TasklineError> {
+        // A version without an operator pins that exact release, `v` or not; ranges
+        // spell out their operator (`^1.2`, `~1.2.3`, `>=1.4, <2`)
+        let (codename, requirement) = match spec.split_once('@') {
+            Some((codename, version)) => {
+                let bare = version.strip_prefix('v').unwrap_or(version);
+                if bare.starts_with(|c: char| c.is_ascii_digit()) {
+                    let exact = Version::parse(version).map_err(|_| TasklineError::VersionError(format!(
+                        "'{}' is not a full version; ranges need an operator such as ^{} or ~{}", version, bare, bare
+                    )))?;
+                    (codename, Some(VersionReq::parse(&format!("={}", exact))?))
+                } else {
+                    (codename, Some(VersionReq::parse(version)?))
+                }
+            }
+            None => (spec, None),
+        };
+        Ok(Self { codename: Codename::parse(codename)?.as_str().to_string(), requirement })
+    }
+}
+
+/// `--locked` and `--offline`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PullOptions {
+    /// Require the version pinned in the lockfile
+    pub locked: bool,
+    /// Use only the local cache
+    pub offline: bool,
+}
+
+/// A registry with its download cache and the lockfile pinning what was pulled from it
+#[derive(Debug, Clone)]
+pub struct PullSource {
+    pub registry: Registry,
+    pub cache: ScriptCache,
+    pub lock_path: PathBuf,
+}
+
+/// A script written by [`PullSource::pull`]
+#[derive(Debug)]
+pub struct Pulled {
+    pub codename: String,
+    pub version: Version,
+    pub path: PathBuf,
+    /// Caching is best effort; a failure is reported but does not fail the pull
+    pub cache_error: Option<TasklineError>,
+}
+
+/// A lock entry moved to a newer version by [`PullSource::update`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockUpdate {
+    pub codename: String,
+    pub from: String,
+    pub to: Version,
+}
+
+impl PullSource {
+    /// The registry configured at or above `start`, with the default cache and the
+    /// lockfile next to the config
+    pub fn discover(start: &Path) -> Result<Self, TasklineError> {
+        let (registry, root) = Registry::discover(start)?;
+        let cache = ScriptCache::for_registry(&registry)?;
+        Ok(Self { registry, cache, lock_path: Lockfile::path_in(&root) })
+    }
+
+    /// Pull `spec` into `dest`. `check` sees each script before it is used and may refuse it.
+    pub fn pull(
+        &self,
+        spec: &PullSpec,
+        options: PullOptions,
+        dest: &Path,
+        check: impl FnOnce(&str, &str) -> Result<(), TasklineError>,
+    ) -> Result<Pulled, TasklineError> {
+        let codename = spec.codename.as_str();
+        let mut lockfile = Lockfile::load(&self.lock_path)?;
+
+        // The lock entry applies as long as the requirement is unchanged
+        let pinned = lockfile.get(codename)
+            .filter(|entry| spec.requirement.as_ref().map_or(true, |r| r.to_string() == entry.requirement))
+            .cloned();
+        if options.locked && pinned.is_none() {
+            return Err(TasklineError::RegistryError(format!(
+                "{} is not pinned in {} with this requirement; pull without --locked to update it", codename, LOCKFILE_NAME
+            )));
+        }
+
+        let not_cached = |what: String| TasklineError::RegistryError(format!("{}; pull it once without --offline", what));
+
+        // A fresh resolution is pinned only once the script is on disk
+        let (version, content, new_pin) = match pinned {
+            Some(entry) => {
+                let version = entry.locked_version()?;
+                // A verified cache hit avoids contacting the registry at all
+                let cached = match self.cache.load(codename, version) {
+                    Ok(cached) => cached,
+                    Err(e) if options.offline => return Err(e),
+                    Err(_) => None,
+                };
+                let content = match cached {
+                    Some(content) => content,
+                    None if options.offline => return Err(not_cached(format!("{} {} is not in the local cache", codename, version))),
+                    None => self.registry.open()?.read(codename, version)?,
+                };
+                entry.verify(&content)?;
+                (version, content, None)
+            }
+            None => {
+                let req = spec.requirement.clone().unwrap_or(VersionReq::STAR);
+                let (version, content) = if options.offline {
+                    let version = req.highest_match(&self.cache.versions(codename))
+                        .ok_or_else(|| not_cached(format!("No cached version of {} matches '{}'", codename, req)))?;
+                    let content = self.cache.load(codename, version)?
+                        .ok_or_else(|| not_cached(format!("{} {} is not in the local cache", codename, version)))?;
+                    (version, content)
+                } else {
+                    let checkout = self.registry.open()?;
+                    let version = checkout.resolve(codename, &req)?;
+                    (version, checkout.read(codename, version)?)
+                };
+                (version, content, Some(req))
+            }
+        };
+        check(&format!("{} {}", codename, version), &content)?;
+
+        // Writing the script is the first change, so a refused or aborted pull leaves
+        // the lockfile and the cache as they were
+        let path = dest.join(versioned::file_name(codename, version, Some(TASKLINE_EXTENSION)));
+        write_new_file(&path, content.as_bytes(), "Pull")?;
+        if let Some(req) = new_pin {
+            lockfile.record(codename, &req, version, &content);
+            lockfile.save(&self.lock_path)?;
+        }
+        let cache_error = self.cache.store(codename, version, &content).err();
+        Ok(Pulled { codename: codename.to_string(), version, path, cache_error })
+    }
+
+    /// Move lock entries (all, or only `only`) to the newest version their requirement allows
+    pub fn update(&self, only: Option<&str>) -> Result<Vec<LockUpdate>, TasklineError> {
+        let mut lockfile = Lockfile::load(&self.lock_path)?;
+        if let Some(codename) = only {
+            if lockfile.get(codename).is_none() {
+                return Err(TasklineError::RegistryError(format!("{} is not pinned in {}", codename, LOCKFILE_NAME)));
+            }
+        }
+
+        let checkout = self.registry.open()?;
+        let entries: Vec<_> = lockfile.scripts.iter()
+            .filter(|entry| only.map_or(true, |codename| entry.codename == codename))
+            .cloned()
+            .collect();
+
+        let mut updates = Vec::new();
+        for entry in entries {
+            let req = VersionReq::parse(&entry.requirement)?;
+            let newest = checkout.resolve(&entry.codename, &req)?;
+            if newest.to_string() == entry.version {
+                continue;
+            }
+            let content = checkout.read(&entry.codename, newest)?;
+            lockfile.record(&entry.codename, &req, newest, &content);
+            updates.push(LockUpdate { codename: entry.codename, from: entry.version, to: newest });
+        }
+
+        if !updates.is_empty() {
+            lockfile.save(&self.lock_path)?;
+        }
+        Ok(updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SCRIPT: &str = "@Taskline codename deploy\n@Taskline version v1.2.0\n\n-> body\n";
+
+    /// A directory registry holding deploy v1.2.0, v1.9.3 and v2.0.0, with its own cache
+    /// and lockfile, plus an empty destination directory
+    fn fixture() -> (TempDir, PullSource, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = Registry::Directory(temp_dir.path().join("registry"));
+        let checkout = registry.open().unwrap();
+        for version in ["v1.2.0", "v1.9.3", "v2.0.0"] {
+            checkout.publish(&SCRIPT.replace("v1.2.0", version)).unwrap();
+        }
+        let cache = ScriptCache::in_dir(&temp_dir.path().join("cache"), &registry);
+        let source = PullSource { registry, cache, lock_path: Lockfile::path_in(temp_dir.path()) };
+        let dest = temp_dir.path().join("work");
+        std::fs::create_dir(&dest).unwrap();
+        (temp_dir, source, dest)
+    }
+
+    fn pull(source: &PullSource, spec: &str, options: PullOptions, dest: &Path) -> Result<Pulled, TasklineError> {
+        source.pull(&PullSpec::parse(spec).unwrap(), options, dest, |_, _| Ok(()))
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let exact = PullSpec::parse("deploy@v1.2.0").unwrap();
+        assert_eq!(exact.codename, "deploy");
+        assert_eq!(exact.requirement.unwrap().to_string(), "=1.2.0");
+        assert_eq!(PullSpec::parse("deploy@1.2.0").unwrap(), PullSpec::parse("deploy@v1.2.0").unwrap());
+        assert_eq!(PullSpec::parse("deploy@^1.2").unwrap().requirement.unwrap().to_string(), "^1.2");
+        assert!(PullSpec::parse("deploy@1.2").is_err());
+        assert_eq!(PullSpec::parse("deploy").unwrap().requirement, None);
+        assert!(PullSpec::parse("../evil").is_err());
+        assert!(PullSpec::parse("deploy@not-a-version").is_err());
+    }
+
+    #[test]
+    fn test_exact_pin() {
+        let (_temp_dir, source, dest) = fixture();
+        let pulled = pull(&source, "deploy@v1.2.0", PullOptions::default(), &dest).unwrap();
+        assert_eq!(pulled.version, Version::new(1, 2, 0));
+        assert_eq!(pulled.path, dest.join("deploy_v1.2.0.tskln"));
+        assert_eq!(std::fs::read_to_string(&pulled.path).unwrap(), SCRIPT);
+
+        let lockfile = Lockfile::load(&source.lock_path).unwrap();
+        let entry = lockfile.get("deploy").unwrap();
+        assert_eq!((entry.requirement.as_str(), entry.version.as_str()), ("=1.2.0", "v1.2.0"));
+        assert!(pull(&source, "deploy@v3.0.0", PullOptions::default(), &dest).is_err());
+    }
+
+    #[test]
+    fn test_constraint_picks_highest_match() {
+        let (_temp_dir, source, dest) = fixture();
+        assert_eq!(pull(&source, "deploy@^1.2", PullOptions::default(), &dest).unwrap().version, Version::new(1, 9, 3));
+        assert_eq!(pull(&source, "deploy@>=1.0, <1.9", PullOptions::default(), &dest).unwrap().version, Version::new(1, 2, 0));
+        assert_eq!(pull(&source, "deploy@*", PullOptions::default(), &dest).unwrap().version, Version::new(2, 0, 0));
+
+        let error = pull(&source, "deploy@^3", PullOptions::default(), &dest).unwrap_err();
+        assert!(error.to_string().contains("available: v1.2.0, v1.9.3, v2.0.0"));
+        assert!(pull(&source, "unknown", PullOptions::default(), &dest).is_err());
+    }
+
+    #[test]
+    fn test_lock_is_reused_until_the_requirement_changes() {
+        let (temp_dir, source, dest) = fixture();
+        assert_eq!(pull(&source, "deploy", PullOptions::default(), &dest).unwrap().version, Version::new(2, 0, 0));
+        source.registry.open().unwrap().publish(&SCRIPT.replace("v1.2.0", "v2.1.0")).unwrap();
+
+        let other = temp_dir.path().join("other");
+        std::fs::create_dir(&other).unwrap();
+        let locked = PullOptions { locked: true, offline: false };
+        assert_eq!(pull(&source, "deploy", locked, &other).unwrap().version, Version::new(2, 0, 0));
+
+        let error = pull(&source, "deploy@^1", locked, &other).unwrap_err();
+        assert!(error.to_string().contains("not pinned in taskline.lock"));
+        assert_eq!(pull(&source, "deploy@^1", PullOptions::default(), &other).unwrap().version, Version::new(1, 9, 3));
+        assert_eq!(Lockfile::load(&source.lock_path).unwrap().get("deploy").unwrap().requirement, "^1");
+    }
+
+    #[test]
+    fn test_locked_rejects_unpinned_and_tampered() {
+        let (temp_dir, source, dest) = fixture();
+        let locked = PullOptions { locked: true, offline: false };
+        assert!(pull(&source, "deploy", locked, &dest).is_err());
+        assert!(!source.lock_path.exists());
+
+        pull(&source, "deploy@v1.2.0", PullOptions::default(), &dest).unwrap();
+        std::fs::write(temp_dir.path().join("registry").join("deploy").join("v1.2.0.tskln"), "tampered").unwrap();
+        std::fs::remove_dir_all(temp_dir.path().join("cache")).unwrap();
+        std::fs::remove_file(dest.join("deploy_v1.2.0.tskln")).unwrap();
+        let error = pull(&source, "deploy@v1.2.0", locked, &dest).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    fn test_aborted_pull_changes_nothing() {
+        let (temp_dir, source, dest) = fixture();
+        pull(&source, "deploy@^1", PullOptions::default(), &dest).unwrap();
+        let lock_before = std::fs::read_to_string(&source.lock_path).unwrap();
+        std::fs::remove_dir_all(temp_dir.path().join("cache")).unwrap();
+        std::fs::write(dest.join("deploy_v2.0.0.tskln"), "local edits").unwrap();
+
+        let error = pull(&source, "deploy", PullOptions::default(), &dest).unwrap_err();
+        assert!(error.to_string().contains("Pull aborted to prevent overwriting"));
+        assert_eq!(std::fs::read_to_string(dest.join("deploy_v2.0.0.tskln")).unwrap(), "local edits");
+        assert_eq!(std::fs::read_to_string(&source.lock_path).unwrap(), lock_before);
+        assert!(source.cache.versions("deploy").is_empty());
+    }
+
+    #[test]
+    fn test_update() {
+        let (_temp_dir, source, dest) = fixture();
+        pull(&source, "deploy@^1", PullOptions::default(), &dest).unwrap();
+        assert!(source.update(None).unwrap().is_empty());
+        assert!(source.update(Some("unknown")).is_err());
+
+        source.registry.open().unwrap().publish(&SCRIPT.replace("v1.2.0", "v1.10.0")).unwrap();
+        let updates = source.update(Some("deploy")).unwrap();
+        assert_eq!(updates, [LockUpdate { codename: "deploy".to_string(), from: "v1.9.3".to_string(), to: Version::new(1, 10, 0) }]);
+        assert_eq!(Lockfile::load(&source.lock_path).unwrap().get("deploy").unwrap().version, "v1.10.0");
+    }
+
+    #[test]
+    fn test_offline_uses_only_the_cache() {
+        let (temp_dir, source, dest) = fixture();
+        let offline = PullOptions { locked: false, offline: true };
+        let error = pull(&source, "deploy", offline, &dest).unwrap_err();
+        assert!(error.to_string().contains("No cached version of deploy matches '*'; pull it once without --offline"));
+        assert!(!source.lock_path.exists());
+
+        pull(&source, "deploy@^1", PullOptions::default(), &dest).unwrap();
+        std::fs::remove_dir_all(temp_dir.path().join("registry")).unwrap();
+        let other = temp_dir.path().join("other");
+        std::fs::create_dir(&other).unwrap();
+
+        // The pinned version comes from the cache, and so does a new constraint it satisfies
+        let locked = PullOptions { locked: true, offline: true };
+        assert_eq!(pull(&source, "deploy@^1", locked, &other).unwrap().version, Version::new(1, 9, 3));
+        std::fs::remove_file(other.join("deploy_v1.9.3.tskln")).unwrap();
+        assert_eq!(pull(&source, "deploy@~1.9", offline, &other).unwrap().version, Version::new(1, 9, 3));
+        assert!(pull(&source, "deploy@^2", offline, &other).is_err());
+    }
+
+    #[test]
+    fn test_check_can_refuse() {
+        let (_temp_dir, source, dest) = fixture();
+        let refuse = |name: &str, _: &str| Err(TasklineError::ParseError(format!("{} refused", name)));
+        let error = source.pull(&PullSpec::parse("deploy").unwrap(), PullOptions::default(), &dest, refuse).unwrap_err();
+        assert!(error.to_string().contains("deploy v2.0.0 refused"));
+        assert!(std::fs::read_dir(&dest).unwrap().next().is_none());
+        assert!(!source.lock_path.exists());
+    }
+}
@@ -0,0 +1,255 @@
+// File: src/registry.rs
+// --- Script registry backing `taskline publish` / `taskline pull`
+// --- Layout: <registry>/<codename>/<version>.tskln (directory or git repository)
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::config::{RegistryConfig, TasklineConfig, CONFIG_FILE_NAME};
+use crate::constants::TASKLINE_EXTENSION;
+use crate::{Codename, TasklineError, TasklineMetadata, Version, VersionReq};
+
+/// Where published scripts live
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Registry {
+    /// A plain directory (local disk, network share, or the root of a static web index)
+    Directory(PathBuf),
+    /// A git repository; publishing commits and pushes
+    Git { url: String, branch: Option<String> },
+}
+
+/// A readable (and, for publishing, writable) view of a registry
+pub struct RegistryCheckout {
+    root: PathBuf,
+    git: Option<GitCheckout>,
+}
+
+struct GitCheckout {
+    // Keeps the temporary clone alive for the checkout's lifetime
+    _dir: tempfile::TempDir,
+    branch: Option<String>,
+}
+
+impl Registry {
+    /// Build a registry from config; relative directories resolve against `config_root`
+    pub fn from_config(config: &RegistryConfig, config_root: &Path) -> Result<Self, TasklineError> {
+        match (&config.path, &config.git) {
+            (Some(path), None) => Ok(Registry::Directory(config_root.join(path))),
+            (None, Some(url)) => Ok(Registry::Git { url: url.clone(), branch: config.branch.clone() }),
+            (Some(_), Some(_)) => Err(TasklineError::ConfigError("[registry] accepts either 'path' or 'git', not both".to_string())),
+            (None, None) => Err(TasklineError::ConfigError("[registry] needs a 'path' or 'git' location".to_string())),
+        }
+    }
+
+    /// Registry of the nearest `.taskline.toml` at or above `start`, together with the
+    /// directory holding that config (where taskline.lock lives)
+    pub fn discover(start: &Path) -> Result<(Self, PathBuf), TasklineError> {
+        let start = std::fs::canonicalize(start)
+            .map_err(|e| TasklineError::FileError(format!("{}: {}", start.display(), e)))?;
+        let loaded = TasklineConfig::discover(&start)?
+            .ok_or_else(|| TasklineError::ConfigError(format!("No {} found; add a [registry] section", CONFIG_FILE_NAME)))?;
+        let registry = loaded.config.registry
+            .ok_or_else(|| TasklineError::ConfigError(format!("No [registry] section in {}", CONFIG_FILE_NAME)))?;
+        Ok((Registry::from_config(&registry, &loaded.root)?, loaded.root))
+    }
+
+    /// Open the registry; git registries are shallow-cloned into a temporary directory
+    pub fn open(&self) -> Result<RegistryCheckout, TasklineError> {
+        match self {
+            Registry::Directory(root) => Ok(RegistryCheckout { root: root.clone(), git: None }),
+            Registry::Git { url, branch } => {
+                let dir = tempfile::TempDir::new()
+                    .map_err(|e| TasklineError::RegistryError(format!("Failed to create clone directory: {}", e)))?;
+                let mut cmd = Command::new("git");
+                cmd.args(["clone", "--quiet", "--depth", "1"]);
+                if let Some(branch) = branch {
+                    cmd.args(["--branch", branch]);
+                }
+                cmd.arg(url).arg(dir.path());
+                run_git(&mut cmd)?;
+                Ok(RegistryCheckout {
+                    root: dir.path().to_path_buf(),
+                    git: Some(GitCheckout { _dir: dir, branch: branch.clone() }),
+                })
+            }
+        }
+    }
+}
+
+impl RegistryCheckout {
+    /// All published versions of `codename`, ascending
+    pub fn versions(&self, codename: &str) -> Result<Vec<Version>, TasklineError> {
+        check_codename(codename)?;
+        let dir = self.root.join(codename);
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(TasklineError::RegistryError(format!("{}: {}", dir.display(), e))),
+        };
+
+        let mut versions: Vec<Version> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let stem = name.strip_suffix(TASKLINE_EXTENSION)?.strip_suffix('.')?;
+                Version::parse(stem).ok()
+            })
+            .collect();
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// Highest published version of `codename` satisfying `req`
+    pub fn resolve(&self, codename: &str, req: &VersionReq) -> Result<Version, TasklineError> {
+        let versions = self.versions(codename)?;
+        if versions.is_empty() {
+            return Err(TasklineError::RegistryError(format!("{} is not published", codename)));
+        }
+        req.highest_match(&versions).ok_or_else(|| {
+            let available: Vec<String> = versions.iter().map(Version::to_string).collect();
+            TasklineError::RegistryError(format!(
+                "No published version of {} matches '{}' (available: {})", codename, req, available.join(", ")
+            ))
+        })
+    }
+
+    /// Read a published script
+    pub fn read(&self, codename: &str, version: Version) -> Result<String, TasklineError> {
+        check_codename(codename)?;
+        let path = self.script_path(codename, version);
+        std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => TasklineError::RegistryError(format!("{} {} is not published", codename, version)),
+            _ => TasklineError::RegistryError(format!("{}: {}", path.display(), e)),
+        })
+    }
+
+    /// Publish script content under its header codename/version; published versions are immutable
+    pub fn publish(&self, content: &str) -> Result<PublishedScript, TasklineError> {
+        let metadata = TasklineMetadata::parse(content);
+        check_codename(&metadata.codename)?;
+        let version = metadata.version.ok_or_else(|| {
+            TasklineError::RegistryError("Only versioned scripts can be published (missing @Taskline version)".to_string())
+        })?;
+
+        let path = self.script_path(&metadata.codename, version);
+        if path.exists() {
+            return Err(TasklineError::RegistryError(format!("{} {} is already published", metadata.codename, version)));
+        }
+        std::fs::create_dir_all(path.parent().unwrap_or(&self.root))
+            .and_then(|_| std::fs::write(&path, content))
+            .map_err(|e| TasklineError::RegistryError(format!("{}: {}", path.display(), e)))?;
+
+        if let Some(git) = &self.git {
+            let relative = path.strip_prefix(&self.root).unwrap_or(&path);
+            run_git(Command::new("git").arg("-C").arg(&self.root).arg("add").arg(relative))?;
+            run_git(Command::new("git").arg("-C").arg(&self.root)
+                .args(["commit", "--quiet", "-m", &format!("Publish {} {}", metadata.codename, version)]))?;
+            let mut push = Command::new("git");
+            push.arg("-C").arg(&self.root).args(["push", "--quiet", "origin"]);
+            match &git.branch {
+                Some(branch) => push.arg(format!("HEAD:{}", branch)),
+                None => push.arg("HEAD"),
+            };
+            run_git(&mut push)?;
+        }
+
+        Ok(PublishedScript { codename: metadata.codename, version })
+    }
+
+    fn script_path(&self, codename: &str, version: Version) -> PathBuf {
+        self.root.join(codename).join(format!("{}.{}", version, TASKLINE_EXTENSION))
+    }
+}
+
+/// Identity of a script stored in the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedScript {
+    pub codename: String,
+    pub version: Version,
+}
+
+/// Codenames become directory names, so they must not escape the registry
+fn check_codename(codename: &str) -> Result<(), TasklineError> {
+    Codename::parse(codename).map(drop)
+}
+
+fn run_git(cmd: &mut Command) -> Result<(), TasklineError> {
+    let output = cmd.output()
+        .map_err(|e| TasklineError::RegistryError(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(TasklineError::RegistryError(format!(
+            "git failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SCRIPT: &str = "@Taskline codename deploy\n@Taskline version v1.2.0\n\n-> body\n";
+
+    #[test]
+    fn test_directory_publish_and_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkout = Registry::Directory(temp_dir.path().to_path_buf()).open().unwrap();
+
+        let published = checkout.publish(SCRIPT).unwrap();
+        assert_eq!(published, PublishedScript { codename: "deploy".to_string(), version: Version::new(1, 2, 0) });
+        checkout.publish(&SCRIPT.replace("v1.2.0", "v1.10.0")).unwrap();
+        checkout.publish(&SCRIPT.replace("v1.2.0", "v1.9.3")).unwrap();
+
+        assert_eq!(checkout.versions("deploy").unwrap(), vec![
+            Version::new(1, 2, 0), Version::new(1, 9, 3), Version::new(1, 10, 0),
+        ]);
+        assert_eq!(checkout.read("deploy", Version::new(1, 2, 0)).unwrap(), SCRIPT);
+        assert!(checkout.versions("unknown").unwrap().is_empty());
+
+        assert_eq!(checkout.resolve("deploy", &VersionReq::parse("^1.2").unwrap()).unwrap(), Version::new(1, 10, 0));
+        assert_eq!(checkout.resolve("deploy", &VersionReq::parse("~1.9").unwrap()).unwrap(), Version::new(1, 9, 3));
+        assert!(checkout.resolve("deploy", &VersionReq::parse("^2").unwrap()).is_err());
+        assert!(checkout.resolve("unknown", &VersionReq::STAR).is_err());
+    }
+
+    #[test]
+    fn test_publish_rejects_duplicates_and_unversioned() {
+        let temp_dir = TempDir::new().unwrap();
+        let checkout = Registry::Directory(temp_dir.path().to_path_buf()).open().unwrap();
+
+        checkout.publish(SCRIPT).unwrap();
+        assert!(checkout.publish(SCRIPT).is_err());
+        assert!(checkout.publish("@Taskline codename deploy\n\n").is_err());
+        assert!(checkout.publish("@Taskline codename ../evil\n@Taskline version v1.0.0\n").is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        let root = Path::new("/repo");
+        let dir = RegistryConfig { path: Some("registry".into()), ..Default::default() };
+        assert_eq!(Registry::from_config(&dir, root).unwrap(), Registry::Directory(root.join("registry")));
+
+        let git = RegistryConfig { git: Some("https://example.com/scripts.git".into()), ..Default::default() };
+        assert!(matches!(Registry::from_config(&git, root).unwrap(), Registry::Git { .. }));
+
+        assert!(Registry::from_config(&RegistryConfig::default(), root).is_err());
+    }
+
+    #[test]
+    fn test_discover() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("scripts").join("ci");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert!(Registry::discover(&nested).is_err());
+
+        std::fs::write(temp_dir.path().join(CONFIG_FILE_NAME), "[naming]\n").unwrap();
+        assert!(Registry::discover(&nested).is_err());
+
+        std::fs::write(temp_dir.path().join(CONFIG_FILE_NAME), "[registry]\npath = \"registry\"\n").unwrap();
+        let (registry, root) = Registry::discover(&nested).unwrap();
+        let root_dir = std::fs::canonicalize(temp_dir.path()).unwrap();
+        assert_eq!(registry, Registry::Directory(root_dir.join("registry")));
+        assert_eq!(root, root_dir);
+    }
+}
@@ -0,0 +1,143 @@
+// File: src/schema.rs
+// --- JSON Schema of the script metadata model (`taskline schema`)
+// --- Validation mirrors the schema with strict serde types plus the core parsers
+
+use serde::Deserialize;
+use crate::requirements::ToolRequirement;
+use crate::{Author, Codename, TasklineError, Version};
+
+/// JSON Schema (draft 2020-12) describing exported script metadata
+pub const METADATA_SCHEMA: &str = include_str!("metadata.schema.json");
+
+/// Metadata as exported to JSON; unknown keys are rejected like `additionalProperties: false`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MetadataJson {
+    codename: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    authors: Vec<AuthorJson>,
+    #[serde(default)]
+    created: Option<String>,
+    #[serde(default)]
+    expires: Option<String>,
+    #[serde(default)]
+    license: Option<String>,
+    #[serde(default)]
+    deprecated: Option<String>,
+    #[serde(default)]
+    path: Vec<String>,
+    #[serde(default)]
+    requires: Vec<String>,
+    #[serde(default, rename = "requires-taskline")]
+    requires_taskline: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AuthorJson {
+    name: String,
+    #[serde(default)]
+    email: Option<String>,
+}
+
+/// Check a metadata JSON document against [`METADATA_SCHEMA`]
+pub fn validate_metadata_json(json: &str) -> Result<(), TasklineError> {
+    let invalid = |message: String| TasklineError::ParseError(format!("Metadata does not match the schema: {}", message));
+    let metadata: MetadataJson = serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+
+    Codename::parse(&metadata.codename).map_err(|e| invalid(e.to_string()))?;
+    if let Some(version) = &metadata.version {
+        Version::parse(version).map_err(|e| invalid(format!("version '{}': {}", version, e)))?;
+    }
+    for author in &metadata.authors {
+        let line = match &author.email {
+            Some(email) => format!("{} <{}>", author.name, email),
+            None => author.name.clone(),
+        };
+        Author::parse(&line).map_err(|e| invalid(e.to_string()))?;
+    }
+    if let Some(created) = &metadata.created {
+        if !is_date(created) {
+            return Err(invalid(format!("created '{}' is not a YYYY-MM-DD date", created)));
+        }
+    }
+    if let Some(expires) = &metadata.expires {
+        if !is_date(expires) {
+            return Err(invalid(format!("expires '{}' is not a YYYY-MM-DD date", expires)));
+        }
+    }
+    if let Some(license) = &metadata.license {
+        crate::license::validate_license(license).map_err(|e| invalid(e.to_string()))?;
+    }
+    if metadata.deprecated.as_deref().is_some_and(|message| message.contains(['\r', '\n'])) {
+        return Err(invalid("deprecated message must be a single line".to_string()));
+    }
+    if metadata.path.iter().any(String::is_empty) {
+        return Err(invalid("path entries must not be empty".to_string()));
+    }
+    for requires in &metadata.requires {
+        ToolRequirement::parse_list(requires).map_err(|e| invalid(e.to_string()))?;
+    }
+    if let Some(req) = &metadata.requires_taskline {
+        crate::VersionReq::parse(req).map_err(|e| invalid(format!("requires-taskline '{}': {}", req, e)))?;
+    }
+    Ok(())
+}
+
+fn is_date(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() == 10
+        && b[4] == b'-'
+        && b[7] == b'-'
+        && b.iter().enumerate().all(|(i, c)| i == 4 || i == 7 || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_matches_validator() {
+        let schema: serde_json::Value = serde_json::from_str(METADATA_SCHEMA).unwrap();
+        let mut properties: Vec<&str> = schema["properties"].as_object().unwrap().keys().map(String::as_str).collect();
+        properties.sort_unstable();
+        assert_eq!(properties, ["authors", "codename", "created", "deprecated", "expires", "license", "path", "requires", "requires-taskline", "version"]);
+        assert_eq!(schema["required"], serde_json::json!(["codename"]));
+    }
+
+    #[test]
+    fn test_validate_metadata_json() {
+        assert!(validate_metadata_json(r#"{"codename": "deploy"}"#).is_ok());
+        assert!(validate_metadata_json(
+            r#"{"codename": "deploy", "version": "v1.2.3", "authors": [{"name": "Ada", "email": "ada@example.com"}, {"name": "Ops"}], "created": "2025-01-31", "expires": "2026-01-31", "license": "MIT OR Apache-2.0",
+                "deprecated": "use deploy-v2.tskln",
+                "path": ["bin"], "requires": ["node >= 18, docker"], "requires-taskline": ">=0.3"}"#
+        ).is_ok());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "version": null}"#).is_ok());
+
+        assert!(validate_metadata_json(r#"{"version": "v1.0.0"}"#).is_err()); // Missing codename
+        assert!(validate_metadata_json(r#"{"codename": "../etc"}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "version": "1.0"}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "created": "yesterday"}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "expires": "soon"}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "owner": "ops"}"#).is_err()); // Unknown key
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "license": "MIT OR"}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "authors": [{"name": "Ada", "email": "nope"}]}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "authors": ["Ada"]}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "requires": ["node >>= 1"]}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "deprecated": "gone\nfor good"}"#).is_err());
+        assert!(validate_metadata_json(r#"{"codename": "deploy", "requires-taskline": "newest"}"#).is_err());
+        assert!(validate_metadata_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_serialized_metadata_matches_schema() {
+        let content = "@Taskline codename deploy\n@Taskline version 1.2.3\n@Taskline author Ops\n@Taskline deprecated\n@Taskline path bin\n";
+        let metadata = crate::TasklineMetadata::validate(content).unwrap();
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(validate_metadata_json(&json).is_ok(), "{}", json);
+        assert!(validate_metadata_json(&serde_json::to_string(&crate::TasklineMetadata::parse("")).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+// File: src/telemetry.rs
+// --- Strictly opt-in usage telemetry: command counts and error categories, kept locally
+// --- Nothing is recorded until `taskline telemetry on`; arguments and paths are never stored
+// --- Nothing is transmitted either: `taskline telemetry export` prints a report to share by hand
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::TasklineError;
+
+/// Overrides the location of the telemetry file
+pub const TELEMETRY_FILE_ENV_VAR: &str = "TASKLINE_TELEMETRY_FILE";
+
+/// Set to a non-empty value other than `0` to disable recording even when opted in
+pub const DO_NOT_TRACK_ENV_VAR: &str = "DO_NOT_TRACK";
+
+/// Consent and locally collected counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Telemetry {
+    /// Whether the user opted in
+    #[serde(default)]
+    pub enabled: bool,
+    /// Invocations per subcommand name
+    #[serde(default)]
+    pub commands: BTreeMap<String, u64>,
+    /// Failures per error category (`file`, `registry`, ...)
+    #[serde(default)]
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl Telemetry {
+    /// Platform location: `$TASKLINE_TELEMETRY_FILE`, `$XDG_CONFIG_HOME/taskline/telemetry.toml`,
+    /// `~/.config/taskline/telemetry.toml`, or `%APPDATA%\taskline\telemetry.toml` on Windows
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(file) = std::env::var_os(TELEMETRY_FILE_ENV_VAR) {
+            return Some(PathBuf::from(file));
+        }
+        let dir = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+            Some(PathBuf::from(dir))
+        } else {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+        };
+        dir.map(|dir| dir.join("taskline").join("telemetry.toml"))
+    }
+
+    /// Load the file; a missing file means telemetry was never enabled
+    pub fn load(path: &Path) -> Result<Self, TasklineError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| TasklineError::ConfigError(format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(TasklineError::FileError(format!("{}: {}", path.display(), e))),
+        }
+    }
+
+    /// Write the file, creating its directory
+    pub fn save(&self, path: &Path) -> Result<(), TasklineError> {
+        let io = |e: std::io::Error| TasklineError::FileError(format!("{}: {}", path.display(), e));
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io)?;
+        }
+        let content = toml::to_string(self)
+            .map_err(|e| TasklineError::ConfigError(format!("Cannot serialize telemetry: {}", e)))?;
+        std::fs::write(path, content).map_err(io)
+    }
+
+    /// Opt in, or opt out and discard everything collected so far
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            *self = Self::default();
+        }
+        self.enabled = enabled;
+    }
+
+    /// Count one invocation of `command`
+    pub fn record_command(&mut self, command: &str) {
+        if self.enabled {
+            *self.commands.entry(command.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Count one failure in the category of `error`; the message itself is not kept
+    pub fn record_error(&mut self, error: &TasklineError) {
+        if self.enabled {
+            *self.errors.entry(error_category(error).to_string()).or_default() += 1;
+        }
+    }
+}
+
+/// Version of the [`Report`] layout
+pub const REPORT_FORMAT: u32 = 1;
+
+/// The collected counts as `taskline telemetry export` prints them for sharing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// [`REPORT_FORMAT`]
+    pub format: u32,
+    /// Version of the CLI that produced the report
+    pub taskline_version: String,
+    /// `linux`, `macos`, `windows`, ...
+    pub os: &'static str,
+    /// `x86_64`, `aarch64`, ...
+    pub arch: &'static str,
+    /// Invocations per subcommand name
+    pub commands: BTreeMap<String, u64>,
+    /// Failures per error category
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl Telemetry {
+    /// Everything collected, with the CLI version and platform; refused unless the user opted in
+    pub fn report(&self, taskline_version: &str) -> Result<Report, TasklineError> {
+        if !self.enabled {
+            return Err(TasklineError::ConfigError("Telemetry is disabled, so nothing was collected; enable it with 'taskline telemetry on'".to_string()));
+        }
+        Ok(Report {
+            format: REPORT_FORMAT,
+            taskline_version: taskline_version.to_string(),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            commands: self.commands.clone(),
+            errors: self.errors.clone(),
+        })
+    }
+}
+
+/// Whether `DO_NOT_TRACK` asks for no recording
+pub fn do_not_track() -> bool {
+    std::env::var(DO_NOT_TRACK_ENV_VAR).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Update the default telemetry file if the user opted in. Telemetry must never get in
+/// the way of a command, so every failure here is ignored.
+pub fn record(update: impl FnOnce(&mut Telemetry)) {
+    if do_not_track() {
+        return;
+    }
+    let Some(path) = Telemetry::default_path() else { return };
+    let Ok(mut telemetry) = Telemetry::load(&path) else { return };
+    if telemetry.enabled {
+        update(&mut telemetry);
+        let _ = telemetry.save(&path);
+    }
+}
+
+fn error_category(error: &TasklineError) -> &'static str {
+    match error {
+        TasklineError::VersionError(_) => "version",
+        TasklineError::FileError(_) => "file",
+        TasklineError::ParseError(_) => "parse",
+        TasklineError::ConfigError(_) => "config",
+        TasklineError::RegistryError(_) => "registry",
+        TasklineError::EncryptionError(_) => "encryption",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_only_when_enabled() {
+        let mut telemetry = Telemetry::default();
+        telemetry.record_command("pull");
+        assert!(telemetry.commands.is_empty());
+
+        telemetry.set_enabled(true);
+        telemetry.record_command("pull");
+        telemetry.record_command("pull");
+        telemetry.record_error(&TasklineError::RegistryError("https://secret@example.com".to_string()));
+        assert_eq!(telemetry.commands["pull"], 2);
+        assert_eq!(telemetry.errors["registry"], 1);
+
+        telemetry.set_enabled(false);
+        assert_eq!(telemetry, Telemetry::default());
+    }
+
+    #[test]
+    fn test_report_requires_consent() {
+        let mut telemetry = Telemetry::default();
+        assert!(telemetry.report("0.1.0").is_err());
+
+        telemetry.set_enabled(true);
+        telemetry.record_command("pull");
+        let report = telemetry.report("0.1.0").unwrap();
+        assert_eq!((report.format, report.taskline_version.as_str()), (REPORT_FORMAT, "0.1.0"));
+        assert_eq!(report.commands, telemetry.commands);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("telemetry.toml");
+        assert_eq!(Telemetry::load(&path).unwrap(), Telemetry::default());
+
+        let mut telemetry = Telemetry::default();
+        telemetry.set_enabled(true);
+        telemetry.record_command("info");
+        telemetry.save(&path).unwrap();
+        assert_eq!(Telemetry::load(&path).unwrap(), telemetry);
+    }
+}
@@ -0,0 +1,165 @@
+// File: src/tool_versions.rs
+// --- Tool versions pinned by asdf (`.tool-versions`) and mise (`.mise.toml`)
+// --- Read from the script's directory upwards; the nearest pin of each tool wins
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::version_req::{Comparator, Op};
+use crate::{TasklineError, VersionReq};
+
+/// asdf's pin file
+pub const TOOL_VERSIONS_FILE: &str = ".tool-versions";
+
+/// mise's config file
+pub const MISE_FILE: &str = ".mise.toml";
+
+type PinParser = fn(&str) -> Result<Vec<(String, String)>, String>;
+
+/// A tool version pinned by a version manager
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedTool {
+    /// Version as written, e.g. `20.11.1` or `lts`
+    pub spec: String,
+    /// Versions the pin allows, as precise as it is written: `20.11.1` is `=20.11.1`
+    /// and `3.12` is `=3.12` (any 3.12.x). `None` for aliases such as `latest` or `system`
+    pub range: Option<VersionReq>,
+    /// File that pins it
+    pub source: PathBuf,
+}
+
+/// Collect pins from `dir` and its ancestors. In one directory `.mise.toml` takes
+/// precedence over `.tool-versions`, as in mise itself.
+pub fn discover(dir: &Path) -> Result<BTreeMap<String, PinnedTool>, TasklineError> {
+    let mut pins = BTreeMap::new();
+    // Relative paths such as `.` have no ancestors to walk
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    for dir in dir.ancestors() {
+        let files: [(&str, PinParser); 2] = [(MISE_FILE, parse_mise), (TOOL_VERSIONS_FILE, parse_tool_versions)];
+        for (file, parse) in files {
+            let path = dir.join(file);
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(TasklineError::FileError(format!("{}: {}", path.display(), e))),
+            };
+            let entries = parse(&content).map_err(|e| TasklineError::ConfigError(format!("{}: {}", path.display(), e)))?;
+            for (tool, spec) in entries {
+                pins.entry(canonical_tool(&tool).to_string()).or_insert_with(|| PinnedTool {
+                    range: pinned_range(&spec),
+                    spec,
+                    source: path.clone(),
+                });
+            }
+        }
+    }
+    Ok(pins)
+}
+
+/// `tool version [fallback...]` lines; `#` starts a comment
+fn parse_tool_versions(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or_default();
+        let mut fields = line.split_whitespace();
+        if let Some(tool) = fields.next() {
+            let version = fields.next().ok_or_else(|| format!("no version given for '{}'", tool))?;
+            entries.push((tool.to_string(), version.to_string()));
+        }
+    }
+    Ok(entries)
+}
+
+/// The `[tools]` table: `node = "20"`, `python = ["3.12", "3.11"]` or `go = { version = "1.22" }`
+fn parse_mise(content: &str) -> Result<Vec<(String, String)>, String> {
+    let config: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+    let Some(tools) = config.get("tools").and_then(toml::Value::as_table) else { return Ok(Vec::new()) };
+    Ok(tools
+        .iter()
+        .filter_map(|(tool, value)| {
+            let version = match value {
+                toml::Value::Array(versions) => versions.first()?,
+                toml::Value::Table(options) => options.get("version")?,
+                version => version,
+            };
+            Some((tool.clone(), version.as_str()?.to_string()))
+        })
+        .collect())
+}
+
+/// Map plugin names that differ from the executable
+fn canonical_tool(tool: &str) -> &str {
+    match tool {
+        "nodejs" => "node",
+        "golang" => "go",
+        tool => tool,
+    }
+}
+
+fn pinned_range(spec: &str) -> Option<VersionReq> {
+    let numeric = spec.strip_prefix('v').unwrap_or(spec);
+    let end = numeric.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(numeric.len());
+    let mut parts = numeric[..end].split('.').map_while(|part| part.parse::<u32>().ok());
+    let major = parts.next()?;
+    let minor = parts.next();
+    let patch = minor.and_then(|_| parts.next());
+    Some(VersionReq { comparators: vec![Comparator { op: Op::Exact, major, minor, patch }] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_files() {
+        let entries = parse_tool_versions("# pins\nnodejs 20.11.1 18.0.0\npython 3.12  # latest stable\n\n").unwrap();
+        assert_eq!(entries, [("nodejs".to_string(), "20.11.1".to_string()), ("python".to_string(), "3.12".to_string())]);
+        assert!(parse_tool_versions("node\n").is_err());
+
+        let mut entries = parse_mise("[tools]\nnode = \"lts\"\npython = [\"3.11\", \"3.10\"]\ngo = { version = \"1.22\" }\n").unwrap();
+        entries.sort();
+        assert_eq!(entries[0], ("go".to_string(), "1.22".to_string()));
+        assert_eq!(entries[2], ("python".to_string(), "3.11".to_string()));
+        assert!(parse_mise("[env]\nA = \"1\"\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_discover_nearest_wins() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("scripts");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(root.path().join(TOOL_VERSIONS_FILE), "nodejs 16.0.0\ndocker 24.0.5\n").unwrap();
+        std::fs::write(nested.join(TOOL_VERSIONS_FILE), "nodejs 18.0.0\n").unwrap();
+        std::fs::write(nested.join(MISE_FILE), "[tools]\nnode = \"20.1.0\"\npython = \"system\"\n").unwrap();
+
+        let pins = discover(&nested).unwrap();
+        let range = |tool: &str| pins[tool].range.as_ref().map(VersionReq::to_string);
+        assert_eq!(range("node").as_deref(), Some("=20.1.0"));
+        assert_eq!(pins["node"].source, nested.join(MISE_FILE));
+        assert_eq!(range("docker").as_deref(), Some("=24.0.5"));
+        assert_eq!(range("python"), None);
+    }
+
+    #[test]
+    fn test_partial_pins_are_ranges() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join(TOOL_VERSIONS_FILE), "python 3.12
+ruby 3.3.0-preview1
+").unwrap();
+        std::fs::write(root.path().join(MISE_FILE), "[tools]
+node = \"20\"
+go = \"latest\"
+").unwrap();
+
+        let pins = discover(root.path()).unwrap();
+        let range = |tool: &str| pins[tool].range.clone().unwrap();
+        assert_eq!(range("python").to_string(), "=3.12");
+        assert_eq!(range("node").to_string(), "=20");
+        assert_eq!(range("ruby").to_string(), "=3.3.0");
+        assert_eq!(pins["go"].range, None);
+
+        // Any 3.12.x may be installed, so a patch-level minimum is not a contradiction
+        assert!(range("python").intersects(&VersionReq::parse(">=3.12.1").unwrap()));
+        assert!(range("node").intersects(&VersionReq::parse(">=20.4").unwrap()));
+        assert!(!range("python").intersects(&VersionReq::parse(">=3.13").unwrap()));
+    }
+}
@@ -0,0 +1,101 @@
+// File: src/workspace.rs
+// --- Workspace-wide script discovery and parallel validation (`taskline validate --all`)
+// --- Files are checked on a bounded pool of threads; results keep the sorted path order
+// --- Discovery itself lives in taskline-core, which taskline-bump shares
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::naming::NamingConfig;
+use crate::TasklineError;
+
+pub use taskline_core::workspace::{find_scripts, write_new_file};
+
+/// A script that failed validation
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// The script
+    pub path: PathBuf,
+    /// Why it is invalid
+    pub error: TasklineError,
+}
+
+/// Validate each script's header and codename (also against `naming`, when given) using up to
+/// `jobs` threads. Diagnostics come back in the order of `paths`.
+pub fn validate_scripts(paths: &[PathBuf], naming: Option<&NamingConfig>, jobs: usize) -> Vec<Diagnostic> {
+    let validate = |path: &Path| -> Result<(), TasklineError> {
+        let metadata = crate::scan::read_metadata(path)?;
+        crate::Codename::parse(&metadata.codename)?;
+        match naming {
+            Some(naming) => naming.check_codename(&metadata.codename),
+            None => Ok(()),
+        }
+    };
+
+    let next = AtomicUsize::new(0);
+    let mut failures: Vec<(usize, TasklineError)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.clamp(1, paths.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut failures = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else { break failures };
+                        if let Err(error) = validate(path) {
+                            failures.push((i, error));
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().expect("validation worker panicked")).collect()
+    });
+    failures.sort_by_key(|(i, _)| *i);
+    failures.into_iter().map(|(i, error)| Diagnostic { path: paths[i].clone(), error }).collect()
+}
+
+/// Default number of validation threads
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_validate() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = root.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        for i in 0..40 {
+            write(&format!("scripts/ok-{:02}.tskln", i), &format!("@Taskline codename ok-{:02}\n@Taskline version v1.0.0\n\n", i));
+        }
+        write("scripts/bad-version.tskln", "@Taskline codename bad\n@Taskline version 1.0\n\n");
+        write("nested/deeper/no-codename.tskln", "@Taskline version v1.0.0\n\n");
+        write("nested/Upper.tskln", "@Taskline codename Upper\n\n");
+        write(".git/ignored.tskln", "broken");
+        write("target/ignored.tskln", "broken");
+        write("notes.txt", "not a script");
+
+        let scripts = find_scripts(root.path()).unwrap();
+        assert_eq!(scripts.len(), 43);
+        assert!(scripts.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut naming = NamingConfig::default();
+        naming.codename.case = Some(crate::naming::CaseStyle::Kebab);
+        for jobs in [1, 8] {
+            let diagnostics = validate_scripts(&scripts, Some(&naming), jobs);
+            let failed: Vec<_> = diagnostics.iter().map(|d| d.path.strip_prefix(root.path()).unwrap().to_path_buf()).collect();
+            assert_eq!(failed, [
+                PathBuf::from("nested/Upper.tskln"),
+                PathBuf::from("nested/deeper/no-codename.tskln"),
+                PathBuf::from("scripts/bad-version.tskln"),
+            ]);
+        }
+        assert_eq!(validate_scripts(&scripts, None, 4).len(), 2);
+        assert!(validate_scripts(&[], None, 4).is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+// File: taskline-bump/src/conventional.rs
+// --- `--auto`: pick the bump level from conventional commits touching the script
+// --- Also lists the scripts `--changed-since` bumps; only git is shelled out to
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::BumpType;
+
+/// Level a single commit asks for: `feat` is minor, `fix` and `perf` are patch, and a
+/// `!` before the colon or a `BREAKING CHANGE:` footer is major. `None` for types that do
+/// not release (`docs`, `chore`, ...) and for messages that are not conventional commits.
+pub fn classify(message: &str) -> Option<BumpType> {
+    let message = message.trim();
+    let (prefix, _) = message.lines().next()?.split_once(':')?;
+    let marked = prefix.ends_with('!');
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let kind = match prefix.split_once('(') {
+        Some((kind, scope)) if scope.ends_with(')') => kind,
+        Some(_) => return None,
+        None => prefix,
+    };
+    if kind.is_empty() || !kind.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    let footer = message.lines().skip(1).any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"));
+    if marked || footer {
+        return Some(BumpType::Major);
+    }
+    match kind.to_ascii_lowercase().as_str() {
+        "feat" => Some(BumpType::Minor),
+        "fix" | "perf" => Some(BumpType::Patch),
+        _ => None,
+    }
+}
+
+/// Description of a conventional commit: its first line after `type(scope): `
+pub fn description(message: &str) -> Option<&str> {
+    classify(message)?;
+    let (_, description) = message.trim().lines().next()?.split_once(':')?;
+    Some(description.trim())
+}
+
+/// Highest level asked for by any of `messages`
+pub fn bump_level<'a>(messages: impl IntoIterator<Item = &'a str>) -> Option<BumpType> {
+    messages.into_iter().filter_map(classify).max()
+}
+
+/// Tag of the script's current version: `<base>-vX.Y.Z` when it exists, otherwise
+/// `vX.Y.Z` (what the `git tag $TASKLINE_NEW_VERSION` post-bump hook creates)
+pub fn version_tag(dir: &Path, base: &str, version: &str) -> Result<Option<String>, Box<dyn Error>> {
+    for tag in [format!("{}-{}", base, version), version.to_string()] {
+        let output = git(dir)
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("refs/tags/{}^{{commit}}", tag))
+            .output()?;
+        if output.status.success() {
+            return Ok(Some(tag));
+        }
+    }
+    Ok(None)
+}
+
+/// Messages of the commits touching `file` after `since`, or in all history without a
+/// tag. Renames are followed, since every bump renames the script.
+pub fn commit_messages(file: &Path, since: Option<&str>) -> Result<Vec<String>, Box<dyn Error>> {
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = file.file_name().ok_or("the script path has no file name")?;
+    let mut cmd = git(dir);
+    cmd.args(["log", "--follow", "--format=%B%x1e"]);
+    if let Some(tag) = since {
+        cmd.arg(format!("{}..HEAD", tag));
+    }
+    let output = cmd.arg("--").arg(name).output().map_err(|e| format!("git could not be started: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git log failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\u{1e}')
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Scripts under `dir` that differ from `since`, committed or not, relative to `dir`.
+/// Deleted scripts are left out.
+pub fn changed_scripts(dir: &Path, since: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let output = git(dir)
+        .args(["diff", "--name-only", "--relative", "--diff-filter=d", since, "--", "*.tskln"])
+        .output()
+        .map_err(|e| format!("git could not be started: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git diff failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+    let relative = |line: &str| if dir == Path::new(".") { PathBuf::from(line) } else { dir.join(line) };
+    Ok(String::from_utf8_lossy(&output.stdout).lines().map(relative).collect())
+}
+
+/// A git command run from `dir`
+pub fn git(dir: &Path) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(dir);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("feat: add --dry-run"), Some(BumpType::Minor));
+        assert_eq!(classify("feat(deploy): add --dry-run\n\nLonger body."), Some(BumpType::Minor));
+        assert_eq!(classify("fix: quote paths"), Some(BumpType::Patch));
+        assert_eq!(classify("perf(io): buffer writes"), Some(BumpType::Patch));
+        assert_eq!(classify("Fix: quote paths"), Some(BumpType::Patch));
+        assert_eq!(classify("refactor!: drop the legacy flag"), Some(BumpType::Major));
+        assert_eq!(classify("feat(cli)!: rename --fmt"), Some(BumpType::Major));
+        assert_eq!(classify("fix: x\n\nBREAKING CHANGE: the output format changed"), Some(BumpType::Major));
+        assert_eq!(classify("chore: BREAKING CHANGE: not a footer"), None);
+        assert_eq!(classify("docs: typo"), None);
+        assert_eq!(classify("Update deploy script"), None);
+        assert_eq!(classify("feat(cli: unclosed scope"), None);
+        assert_eq!(classify("fix it: now"), None);
+        assert_eq!(classify(""), None);
+
+        assert_eq!(description("feat(cli)!: rename --fmt\n\nbody"), Some("rename --fmt"));
+        assert_eq!(description("docs: typo"), None);
+    }
+
+    #[test]
+    fn test_bump_level_takes_highest() {
+        assert_eq!(bump_level(["fix: a", "feat: b", "docs: c"]), Some(BumpType::Minor));
+        assert_eq!(bump_level(["fix: a", "feat!: b"]), Some(BumpType::Major));
+        assert_eq!(bump_level(["docs: a", "ci: b"]), None);
+    }
+
+    #[test]
+    fn test_git_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| {
+            let status = git(dir.path())
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false"])
+                .args(args)
+                .output()
+                .map(|output| output.status.success());
+            status.unwrap_or(false)
+        };
+        if !run(&["init", "--quiet"]) {
+            return; // git is not installed
+        }
+        let commit = |file: &str, message: &str| {
+            std::fs::write(dir.path().join(file), message).unwrap();
+            assert!(run(&["add", file]));
+            assert!(run(&["commit", "--quiet", "-m", message]));
+        };
+        commit("deploy.tskln", "feat: first version");
+        assert!(run(&["tag", "deploy-v1.0.0"]));
+        commit("deploy.tskln", "fix: quote paths");
+        commit("other.tskln", "feat!: unrelated script");
+        commit("deploy.tskln", "docs: explain flags");
+
+        let tag = version_tag(dir.path(), "deploy", "v1.0.0").unwrap();
+        assert_eq!(tag.as_deref(), Some("deploy-v1.0.0"));
+        assert_eq!(version_tag(dir.path(), "deploy", "v2.0.0").unwrap(), None);
+        let file = dir.path().join("deploy.tskln");
+        let messages = commit_messages(&file, tag.as_deref()).unwrap();
+        assert_eq!(messages, ["docs: explain flags", "fix: quote paths"]);
+        assert_eq!(bump_level(messages.iter().map(String::as_str)), Some(BumpType::Patch));
+        assert_eq!(commit_messages(&file, None).unwrap().len(), 3);
+
+        std::fs::remove_file(dir.path().join("other.tskln")).unwrap();
+        let changed = changed_scripts(dir.path(), "deploy-v1.0.0").unwrap();
+        assert_eq!(changed, [dir.path().join("deploy.tskln")]);
+    }
+}
@@ -1,20 +1,52 @@
 // File: taskline-bump/src/main.rs  
 // --- Ultra-fast Taskline version bumping with ZERO dependencies bloat
-// --- Separate crate ensures only clap + minimal tokio in final binary
+// --- Separate crate ensures only clap and std I/O in the binary
 
 use std::fs;
-use std::path::Path;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use clap::Parser;
+use taskline_core::config::TasklineConfig;
+use taskline_core::workspace::canonicalize;
+use taskline_core::{LineEnding, Version};
+
+mod conventional;
+mod release;
 
 #[derive(Parser)]
 #[command(name = "bump")]
 struct Args {
-    filename: String,
+    #[arg(required_unless_present = "changed_since")]
+    filename: Option<String>,
+    #[arg(long, required_unless_present_any = ["auto", "changed_since"], conflicts_with = "auto")]
+    fmt: Option<String>,
+    /// Choose the level from the conventional commits touching the script since its version tag
+    #[arg(long)]
+    auto: bool,
+    /// Bump every script changed since this git ref instead of one file; without `--fmt`,
+    /// each gets the level its commits since the ref ask for
+    #[arg(long, value_name = "GIT_REF", conflicts_with = "filename")]
+    changed_since: Option<String>,
+    /// Point `<name>.latest.<ext>` at the bumped file (a copy on Windows)
+    #[arg(long)]
+    link_latest: bool,
+    /// Leave references to the old file name in other scripts and `.taskline.toml` alone
     #[arg(long)]
-    fmt: String,
+    no_update_references: bool,
+    /// Also add a CHANGELOG.md entry, then commit and tag the release
+    #[arg(long, conflicts_with = "changed_since")]
+    release: bool,
+    /// Push the release commit and tag to origin
+    #[arg(long, requires = "release")]
+    push: bool,
+    /// Print what would happen without changing anything; hooks are not run
+    #[arg(long)]
+    dry_run: bool,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum BumpType {
     Patch = 0,
     Minor = 1, 
@@ -49,7 +81,9 @@ fn parse_version_fast(line: &str) -> Option<(u32, u32, u32)> {
         return None;
     }
     
+    // Both `v1.2.3` (canonical) and the legacy `1.2.3` are accepted
     let version_part = &bytes[PREFIX.len()..];
+    let version_part = version_part.strip_prefix(b"v").unwrap_or(version_part);
     let mut parts = [0u32; 3];
     let mut part_idx = 0;
     let mut num = 0u32;
@@ -57,7 +91,8 @@ fn parse_version_fast(line: &str) -> Option<(u32, u32, u32)> {
     for &byte in version_part {
         match byte {
             b'0'..=b'9' => {
-                num = num * 10 + (byte - b'0') as u32;
+                // Reject instead of wrapping/panicking on u32 overflow
+                num = num.checked_mul(10)?.checked_add((byte - b'0') as u32)?;
             }
             b'.' => {
                 if part_idx >= 2 { return None; }
@@ -77,89 +112,416 @@ fn parse_version_fast(line: &str) -> Option<(u32, u32, u32)> {
     }
 }
 
-#[tokio::main(flavor="multi_thread")]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+/// `version: v1.2.3` (optionally quoted) at the top level of a YAML frontmatter block
+fn parse_frontmatter_version(line: &str) -> Option<(u32, u32, u32)> {
+    let value = line.strip_prefix("version:")?.trim().trim_matches(['"', '\'']);
+    Version::parse(value).ok().map(|v| (v.major, v.minor, v.patch))
+}
+
+/// Location of the version line, found by reading only the leading `@Taskline` lines
+/// (or the `--- ... ---` frontmatter block)
+#[derive(Debug, PartialEq, Eq)]
+struct HeaderScan {
+    version: Option<(u32, u32, u32)>,
+    /// Byte range of the version line's text (line ending excluded), or an empty
+    /// range at the insertion point (after the first line) when there is none
+    span: Range<u64>,
+    /// The insertion point follows a final line without a newline
+    needs_newline: bool,
+    /// Dominant line ending of the lines scanned, used for an inserted line
+    line_ending: LineEnding,
+    /// The header is YAML frontmatter, so the line is `version: ...`
+    frontmatter: bool,
+}
+
+/// Scan the header block without touching the body
+fn scan_header<R: BufRead>(mut reader: R) -> io::Result<HeaderScan> {
+    let mut offset = 0u64;
+    let mut buf = Vec::with_capacity(128);
+    let mut insert_at = None;
+    let mut needs_newline = false;
+    let (mut crlf, mut lf) = (0usize, 0usize);
+    let mut frontmatter = false;
     
-    // Ultra-fast format validation
-    let bump_type = BumpType::from_str_fast(&args.fmt)
-        .ok_or("Invalid format. Use ..x (patch), .x. (minor), or x.. (major)")?;
+    loop {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        if buf.ends_with(b"\r\n") {
+            crlf += 1;
+        } else if buf.ends_with(b"\n") {
+            lf += 1;
+        }
+        let text = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let text = text.strip_suffix(b"\r").unwrap_or(text);
+        if insert_at.is_none() {
+            insert_at = Some(offset + n as u64);
+            needs_newline = !buf.ends_with(b"\n");
+        }
+        let Ok(line) = std::str::from_utf8(text) else { break };
+        if offset == 0 && line == "---" {
+            frontmatter = true;
+            offset += n as u64;
+            continue;
+        }
+        let version = if frontmatter { parse_frontmatter_version(line) } else { parse_version_fast(line) };
+        if let Some(version) = version {
+            let span = offset..offset + text.len() as u64;
+            return Ok(HeaderScan { version: Some(version), span, needs_newline: false, line_ending: LineEnding::Lf, frontmatter });
+        }
+        let header_line = line.starts_with("@Taskline ") || taskline_core::is_header_comment(line);
+        if (frontmatter && line == "---") || (!frontmatter && !header_line) {
+            break;
+        }
+        offset += n as u64;
+    }
     
-    // Stream-based file processing for memory efficiency
-    let content = fs::read_to_string(&args.filename)?;
-    let mut lines = Vec::with_capacity(content.lines().count()); // Pre-allocate
-    let mut current_version = (0u32, 0u32, 0u32);
-    let mut version_line_index = None;
+    let at = insert_at.unwrap_or(0);
+    let line_ending = if crlf > lf { LineEnding::CrLf } else { LineEnding::Lf };
+    Ok(HeaderScan { version: None, span: at..at, needs_newline, line_ending, frontmatter })
+}
+
+/// Write `src` to `dst` with the version line replaced (or inserted), leaving every other
+/// byte untouched. A same-length replacement is patched in place; otherwise the body is
+/// streamed into a sibling temp file, so memory use does not grow with the script.
+fn write_bumped(src: &Path, dst: &Path, scan: &HeaderScan, version_line: &str) -> io::Result<()> {
+    let old_len = scan.span.end - scan.span.start;
+    if scan.version.is_some() && old_len == version_line.len() as u64 {
+        let mut file = fs::OpenOptions::new().write(true).open(src)?;
+        file.seek(SeekFrom::Start(scan.span.start))?;
+        file.write_all(version_line.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        return rename(src, dst);
+    }
     
-    // Single-pass parsing with SIMD-friendly iteration
-    for (i, line) in content.lines().enumerate() {
-        if version_line_index.is_none() {
-            if let Some(version) = parse_version_fast(line) {
-                current_version = version;
-                version_line_index = Some(i);
+    let tmp = temp_path_for(dst);
+    let result = (|| {
+        let mut input = BufReader::new(fs::File::open(src)?);
+        let mut output = BufWriter::new(fs::File::create(&tmp)?);
+        io::copy(&mut (&mut input).take(scan.span.start), &mut output)?;
+        if scan.version.is_some() {
+            output.write_all(version_line.as_bytes())?;
+            input.seek_relative(old_len as i64)?;
+        } else if scan.needs_newline {
+            write!(output, "{}{}", scan.line_ending.as_str(), version_line)?;
+        } else {
+            write!(output, "{}{}", version_line, scan.line_ending.as_str())?;
+        }
+        io::copy(&mut input, &mut output)?;
+        output.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        rename(&tmp, dst)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+        return result;
+    }
+    fs::remove_file(src)
+}
+
+/// Point `<base>.latest.<ext>` next to `target` at it. A relative symlink on Unix, so
+/// the directory can move; a copy elsewhere, where symlinks need extra privileges.
+/// The link is replaced atomically, so readers never see it missing.
+fn link_latest(target: &Path) -> io::Result<PathBuf> {
+    let name = target.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let parsed = taskline_core::versioned::parse_file_name(name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{} has no version in its name", target.display())))?;
+    let link = target.with_file_name(match parsed.extension {
+        Some(ext) => format!("{}.latest.{}", parsed.base, ext),
+        None => format!("{}.latest", parsed.base),
+    });
+    let tmp = temp_path_for(&link);
+    let _ = fs::remove_file(&tmp);
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(name, &tmp)?;
+    #[cfg(not(unix))]
+    fs::copy(target, &tmp)?;
+    rename(&tmp, &link)?;
+    Ok(link)
+}
+
+/// `fs::rename`, retried briefly on Windows, where a virus scanner or search indexer that
+/// just opened the file makes the rename fail with a sharing violation
+fn rename(from: &Path, to: &Path) -> io::Result<()> {
+    let mut attempts = 0;
+    loop {
+        match fs::rename(from, to) {
+            Err(e) if cfg!(windows) && attempts < 5 && (e.kind() == io::ErrorKind::PermissionDenied || e.raw_os_error() == Some(32)) => {
+                attempts += 1;
+                std::thread::sleep(std::time::Duration::from_millis(20 << attempts));
             }
+            result => return result,
         }
-        lines.push(line);
     }
-    
-    // Branchless version calculation using lookup table
-    let new_version = match bump_type {
-        BumpType::Patch => (current_version.0, current_version.1, current_version.2 + 1),
-        BumpType::Minor => (current_version.0, current_version.1 + 1, 0),
-        BumpType::Major => (current_version.0 + 1, 0, 0),
+}
+
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!(".{}.bump-tmp", name))
+}
+
+/// Run repository hook commands in order, stopping at the first failure.
+/// Commands go through the platform shell from the config root directory.
+fn run_hooks(stage: &str, commands: &[String], root: &Path, env: &[(&str, &str)]) -> Result<(), Box<dyn std::error::Error>> {
+    for command in commands {
+        let mut cmd = if cfg!(windows) {
+            let mut c = Command::new("cmd");
+            c.arg("/C");
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.arg("-c");
+            c
+        };
+        cmd.arg(command).current_dir(root).envs(env.iter().copied());
+        // As the dispatcher does for its children: no prompts and no colors in CI logs
+        if taskline_core::ci::detect() {
+            cmd.stdin(Stdio::null())
+                .env(taskline_core::ci::CI_ENV_VAR, "1")
+                .env("NO_COLOR", "1")
+                .env("CARGO_TERM_COLOR", "never");
+        }
+
+        let status = cmd.status()
+            .map_err(|e| format!("{} hook '{}' could not be started: {}", stage, command, e))?;
+        if !status.success() {
+            return Err(format!("{} hook '{}' failed ({})", stage, command, status).into());
+        }
+    }
+    Ok(())
+}
+
+/// `--auto` found no commit that asks for a release
+struct NothingToBump(String);
+
+impl std::fmt::Display for NothingToBump {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// Shown by `main` like the plain string errors
+impl std::fmt::Debug for NothingToBump {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl std::error::Error for NothingToBump {}
+
+/// `--auto`: the highest level asked for by the conventional commits touching the script
+/// since `since`, or else since the tag of its current version (all of its history when
+/// there is no such tag)
+fn auto_bump_type(path: &Path, current_version: (u32, u32, u32), since: Option<&str>) -> Result<BumpType, Box<dyn std::error::Error>> {
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
     };
+    let name = path.file_name().and_then(|n| n.to_str()).ok_or("the script path has no file name")?;
+    let (base_ref, label) = match since {
+        Some(since) => (Some(since.to_string()), since.to_string()),
+        None => {
+            let base = taskline_core::versioned::parse_file_name(name)
+                .map(|parsed| parsed.base)
+                .unwrap_or_else(|| name.split('.').next().unwrap_or(name));
+            let version = Version::new(current_version.0, current_version.1, current_version.2).to_string();
+            match conventional::version_tag(dir, base, &version)? {
+                Some(tag) => (Some(tag.clone()), format!("tag {}", tag)),
+                None => (None, "its first commit".to_string()),
+            }
+        }
+    };
+
+    let messages = conventional::commit_messages(path, base_ref.as_deref())?;
+    let bump_type = conventional::bump_level(messages.iter().map(String::as_str))
+        .ok_or_else(|| NothingToBump(format!("No feat, fix or breaking commits touch {} since {}; nothing to bump", name, label)))?;
+    let level = match bump_type {
+        BumpType::Patch => "patch",
+        BumpType::Minor => "minor",
+        BumpType::Major => "major",
+    };
+    println!("Chose a {} bump from {} commit(s) since {}", level, messages.len(), label);
+    Ok(bump_type)
+}
+
+// No async runtime: bumping is a few small reads and writes
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    run()
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    taskline_core::crash::install_panic_hook("taskline-bump", env!("CARGO_PKG_VERSION"));
+    let args = Args::parse();
     
-    // Pre-allocated string builder for version line
-    let mut version_line = String::with_capacity(32);
-    version_line.push_str("@Taskline version ");
-    // Fast integer to string using format! (optimized by compiler)
-    version_line.push_str(&format!("{}.{}.{}", new_version.0, new_version.1, new_version.2));
-    
-    // In-place line replacement or insertion
-    match version_line_index {
-        Some(index) => {
-            lines[index] = &version_line;
+    match (&args.changed_since, &args.filename) {
+        (Some(since), _) => bump_changed(&args, since),
+        (None, Some(filename)) if args.release => release::release(&args, Path::new(filename)),
+        (None, Some(filename)) => {
+            let path = Path::new(filename);
+            bump_file(&args, path, choose_bump_type(&args, path, None)?).map(|_| ())
         }
+        (None, None) => Err("A script or --changed-since is required".into()),
+    }
+}
+
+/// Versions and new path of a finished bump
+struct Bumped {
+    old_version: String,
+    new_version: String,
+    path: PathBuf,
+}
+
+/// Level for one script: `--fmt` when given, otherwise the level its commits ask for since
+/// `since` (or its version tag)
+fn choose_bump_type(args: &Args, path: &Path, since: Option<&str>) -> Result<BumpType, Box<dyn std::error::Error>> {
+    match &args.fmt {
+        // Ultra-fast format validation
+        Some(fmt) => Ok(BumpType::from_str_fast(fmt)
+            .ok_or("Invalid format. Use ..x (patch), .x. (minor), or x.. (major)")?),
         None => {
-            if lines.len() >= 2 {
-                lines.insert(1, &version_line);
-            } else {
-                lines.push(&version_line);
-            }
+            let scan = scan_header(BufReader::new(fs::File::open(path)?))?;
+            auto_bump_type(path, scan.version.unwrap_or((0, 0, 0)), since)
         }
     }
-    
-    // Efficient string joining with pre-calculated capacity
-    let total_len: usize = lines.iter().map(|l| l.len() + 1).sum(); // +1 for newline
-    let mut updated_content = String::with_capacity(total_len);
-    for (i, line) in lines.iter().enumerate() {
-        if i > 0 {
-            updated_content.push('\n');
+}
+
+/// Version after a `bump_type` bump; an error instead of wrapping at the component maximum
+fn next_version(current_version: (u32, u32, u32), bump_type: BumpType) -> Result<(u32, u32, u32), taskline_core::TasklineError> {
+    let current = Version::new(current_version.0, current_version.1, current_version.2);
+    let next = match bump_type {
+        BumpType::Patch => current.bump_patch()?,
+        BumpType::Minor => current.bump_minor()?,
+        BumpType::Major => current.bump_major()?,
+    };
+    Ok((next.major, next.minor, next.patch))
+}
+
+/// `path`'s file name with its version suffix replaced by `version`: `deploy_v1.0.1.tskln`
+/// becomes `deploy_v1.0.2.tskln`, never `deploy_v1.0.1_v1.0.2.tskln`
+fn versioned_file_name(path: &Path, version: Version) -> Result<String, Box<dyn std::error::Error>> {
+    let name = path.file_name().and_then(|name| name.to_str())
+        .ok_or_else(|| format!("{} has no file name that can be versioned", path.display()))?;
+    let (base, extension) = match taskline_core::versioned::parse_file_name(name) {
+        Some(parsed) => (parsed.base, parsed.extension),
+        None => {
+            let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or(name);
+            (stem, path.extension().and_then(|e| e.to_str()))
         }
-        updated_content.push_str(line);
+    };
+    Ok(taskline_core::versioned::file_name(base, version, extension))
+}
+
+/// Bump one script by `bump_type`
+fn bump_file(args: &Args, path: &Path, bump_type: BumpType) -> Result<Bumped, Box<dyn std::error::Error>> {
+    // Only the header is read; the body is never loaded into memory
+    let scan = scan_header(BufReader::new(fs::File::open(path)?))?;
+    let current_version = scan.version.unwrap_or((0, 0, 0));
+    let new_version = next_version(current_version, bump_type)?;
+    
+    let old_version_str = Version::new(current_version.0, current_version.1, current_version.2).to_string();
+    let new_version_str = Version::new(new_version.0, new_version.1, new_version.2).to_string();
+    
+    let new_filename = versioned_file_name(path, Version::new(new_version.0, new_version.1, new_version.2))?;
+    
+    let parent = path.parent().ok_or("The script path has no parent directory")?;
+    let new_path = parent.join(new_filename);
+    
+    if args.dry_run {
+        println!("Would bump {} from {} to {} and rename to {}", path.display(), old_version_str, new_version_str, new_path.display());
+        return Ok(Bumped { old_version: old_version_str, new_version: new_version_str, path: new_path });
     }
     
-    // Atomic file write for safety
-    fs::write(&args.filename, &updated_content)?;
+    // Repository hooks see both versions; a failing pre-bump hook leaves the file untouched
+    let script_dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let loaded_config = TasklineConfig::discover(&canonicalize(script_dir)?)?;
     
-    // Zero-allocation path manipulation
-    let path = Path::new(&args.filename);
-    let stem = path.file_stem().unwrap().to_str().unwrap();
-    let extension = path.extension().and_then(|e| e.to_str());
+    if let Some(loaded) = &loaded_config {
+        let script_path = canonicalize(path)?;
+        run_hooks("pre-bump", &loaded.config.hooks.pre_bump, &loaded.root, &[
+            ("TASKLINE_OLD_VERSION", &old_version_str),
+            ("TASKLINE_NEW_VERSION", &new_version_str),
+            ("TASKLINE_FILE", &script_path.to_string_lossy()),
+        ])?;
+    }
     
-    // Efficient filename construction
-    let new_filename = match extension {
-        Some(ext) => format!("{}_v{}.{}.{}.{}", stem, new_version.0, new_version.1, new_version.2, ext),
-        None => format!("{}_v{}.{}.{}", stem, new_version.0, new_version.1, new_version.2),
+    // Always written in the canonical `vX.Y.Z` form, like taskline-init
+    let version_line = if scan.frontmatter {
+        format!("version: {}", new_version_str)
+    } else {
+        format!("@Taskline version {}", new_version_str)
     };
     
-    if let Some(parent) = path.parent() {
-        let new_path = parent.join(new_filename);
-        fs::rename(&args.filename, &new_path)?;
-        println!("Bumped to version {}.{}.{} and renamed to {}", 
-                 new_version.0, new_version.1, new_version.2, new_path.display());
+    write_bumped(path, &new_path, &scan, &version_line)?;
+    println!("Bumped to version {}.{}.{} and renamed to {}", 
+             new_version.0, new_version.1, new_version.2, new_path.display());
+    
+    // Other scripts and the workspace config may name the old file; point them at the new one
+    if !args.no_update_references {
+        // The workspace, as for `taskline validate --all`: the config root or the current directory
+        let root = loaded_config.as_ref().map_or(Path::new("."), |loaded| loaded.root.as_path());
+        for file in taskline_core::workspace::update_references(root, path, &new_path)? {
+            println!("Updated reference in {}", file.display());
+        }
     }
     
+    if args.link_latest || loaded_config.as_ref().is_some_and(|loaded| loaded.config.bump.link_latest) {
+        let link = link_latest(&new_path)?;
+        println!("Updated {}", link.display());
+    }
+    
+    if let Some(loaded) = &loaded_config {
+        let new_path = canonicalize(&new_path)?;
+        run_hooks("post-bump", &loaded.config.hooks.post_bump, &loaded.root, &[
+            ("TASKLINE_OLD_VERSION", &old_version_str),
+            ("TASKLINE_NEW_VERSION", &new_version_str),
+            ("TASKLINE_FILE", &new_path.to_string_lossy()),
+        ])?;
+    }
+    
+    Ok(Bumped { old_version: old_version_str, new_version: new_version_str, path: new_path })
+}
+
+/// `--changed-since`: bump every script changed since `since`, carrying on past failures,
+/// then report all of them together
+fn bump_changed(args: &Args, since: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let scripts = conventional::changed_scripts(Path::new("."), since)?;
+    if scripts.is_empty() {
+        println!("No scripts changed since {}", since);
+        return Ok(());
+    }
+    
+    let mut report = Vec::with_capacity(scripts.len());
+    let (mut bumped, mut failed) = (0, 0);
+    for script in &scripts {
+        println!("{}:", script.display());
+        let result = choose_bump_type(args, script, Some(since)).and_then(|bump_type| bump_file(args, script, bump_type));
+        let outcome = match result {
+            Ok(done) => {
+                bumped += 1;
+                format!("{} -> {} ({})", done.old_version, done.new_version, done.path.display())
+            }
+            Err(e) if e.is::<NothingToBump>() => "skipped, no feat, fix or breaking commits".to_string(),
+            Err(e) => {
+                failed += 1;
+                format!("failed: {}", e)
+            }
+        };
+        report.push((script, outcome));
+    }
+    
+    println!();
+    println!("Bumped {} of {} script(s) changed since {}:", bumped, scripts.len(), since);
+    for (script, outcome) in report {
+        println!("  {}  {}", script.display(), outcome);
+    }
+    if failed > 0 {
+        return Err(format!("{} script(s) failed to bump", failed).into());
+    }
     Ok(())
 }
 
@@ -191,6 +553,7 @@ mod tests {
         assert_eq!(parse_version_fast("@Taskline version 10.20.30"), Some((10, 20, 30)));
         assert_eq!(parse_version_fast("@Taskline version 0.0.1"), Some((0, 0, 1)));
         assert_eq!(parse_version_fast("@Taskline version 999.999.999"), Some((999, 999, 999)));
+        assert_eq!(parse_version_fast("@Taskline version v1.2.3"), Some((1, 2, 3)));
     }
 
     #[tokio::test]
@@ -232,6 +595,11 @@ mod tests {
             BumpType::Major => (current.0 + 1, 0, 0),
         };
         assert_eq!(major_result, (2, 0, 0));
+        
+        assert_eq!(next_version(current, BumpType::Minor).unwrap(), (1, 3, 0));
+        assert!(next_version((1, 2, u32::MAX), BumpType::Patch).is_err());
+        assert_eq!(next_version((1, 2, u32::MAX), BumpType::Minor).unwrap(), (1, 3, 0));
+        assert!(next_version((u32::MAX, 0, 0), BumpType::Major).is_err());
     }
 
     async fn create_test_file(dir: &TempDir, filename: &str, content: &str) -> String {
@@ -343,38 +711,34 @@ mod tests {
 
     #[tokio::test]
     async fn test_filename_generation_with_extension() {
-        let path = Path::new("my-script.v1.2.3.tskln");
-        let stem = path.file_stem().unwrap().to_str().unwrap();
-        let extension = path.extension().and_then(|e| e.to_str());
+        let new_filename = versioned_file_name(Path::new("my-script.v1.2.3.tskln"), Version::new(2, 0, 0)).unwrap();
+        assert_eq!(new_filename, "my-script_v2.0.0.tskln");
         
-        assert_eq!(stem, "my-script.v1.2.3");
-        assert_eq!(extension, Some("tskln"));
-        
-        let new_version = (2, 0, 0);
-        let new_filename = match extension {
-            Some(ext) => format!("{}_v{}.{}.{}.{}", stem, new_version.0, new_version.1, new_version.2, ext),
-            None => format!("{}_v{}.{}.{}", stem, new_version.0, new_version.1, new_version.2),
-        };
-        
-        assert_eq!(new_filename, "my-script.v1.2.3_v2.0.0.tskln");
+        let new_filename = versioned_file_name(Path::new("my-script.tskln"), Version::new(0, 0, 1)).unwrap();
+        assert_eq!(new_filename, "my-script_v0.0.1.tskln");
     }
 
     #[tokio::test]
     async fn test_filename_generation_without_extension() {
-        let path = Path::new("my-script");
-        let stem = path.file_stem().unwrap().to_str().unwrap();
-        let extension = path.extension().and_then(|e| e.to_str());
-        
-        assert_eq!(stem, "my-script");
-        assert_eq!(extension, None);
-        
-        let new_version = (1, 5, 2);
-        let new_filename = match extension {
-            Some(ext) => format!("{}_v{}.{}.{}.{}", stem, new_version.0, new_version.1, new_version.2, ext),
-            None => format!("{}_v{}.{}.{}", stem, new_version.0, new_version.1, new_version.2),
-        };
-        
+        let new_filename = versioned_file_name(Path::new("my-script"), Version::new(1, 5, 2)).unwrap();
         assert_eq!(new_filename, "my-script_v1.5.2");
+        
+        let new_filename = versioned_file_name(Path::new("my-script_v1.5.2"), Version::new(1, 5, 3)).unwrap();
+        assert_eq!(new_filename, "my-script_v1.5.3");
+    }
+
+    #[test]
+    fn test_bumping_twice_replaces_the_suffix() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut path = temp_dir.path().join("deploy_v1.0.0.tskln");
+        fs::write(&path, "@Taskline version v1.0.0\necho deploy\n").unwrap();
+        for _ in 0..2 {
+            let args = Args::parse_from(["bump", path.to_str().unwrap(), "--fmt", "..x", "--no-update-references"]);
+            path = bump_file(&args, &path, BumpType::Patch).unwrap().path;
+        }
+        assert_eq!(path, temp_dir.path().join("deploy_v1.0.2.tskln"));
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+        assert!(fs::read_to_string(&path).unwrap().starts_with("@Taskline version v1.0.2\n"));
     }
 
     #[tokio::test]
@@ -418,10 +782,7 @@ mod tests {
         let _yet_another_ref = bump_type; // Should still work
         
         // Use original variable
-        match bump_type {
-            BumpType::Patch => assert!(true),
-            _ => assert!(false),
-        }
+        assert!(matches!(bump_type, BumpType::Patch));
     }
 
     #[tokio::test]
@@ -439,6 +800,156 @@ mod tests {
         let _ = result; // Just ensure it doesn't panic
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_hooks_exposes_versions_and_stops_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let commands = vec![
+            "echo \"$TASKLINE_OLD_VERSION -> $TASKLINE_NEW_VERSION\" > hook.out".to_string(),
+        ];
+        let env = [("TASKLINE_OLD_VERSION", "v1.2.3"), ("TASKLINE_NEW_VERSION", "v1.2.4")];
+        
+        run_hooks("pre-bump", &commands, temp_dir.path(), &env).unwrap();
+        let output = tokio::fs::read_to_string(temp_dir.path().join("hook.out")).await.unwrap();
+        assert_eq!(output.trim(), "v1.2.3 -> v1.2.4");
+        
+        // First failure aborts the remaining hooks
+        let commands = vec!["exit 3".to_string(), "touch never.out".to_string()];
+        let err = run_hooks("pre-bump", &commands, temp_dir.path(), &env).unwrap_err();
+        assert!(err.to_string().contains("pre-bump hook 'exit 3' failed"));
+        assert!(!temp_dir.path().join("never.out").exists());
+    }
+
+    #[test]
+    fn test_link_latest_follows_bumps() {
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("deploy.latest.tskln");
+        for version in ["1.9.0", "1.10.0"] {
+            let target = temp_dir.path().join(format!("deploy_v{}.tskln", version));
+            fs::write(&target, version).unwrap();
+            assert_eq!(link_latest(&target).unwrap(), link);
+            assert_eq!(fs::read_to_string(&link).unwrap(), version);
+        }
+        #[cfg(unix)]
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("deploy_v1.10.0.tskln"));
+        assert!(link_latest(&temp_dir.path().join("deploy.tskln")).is_err());
+    }
+
+    #[test]
+    fn test_link_latest_after_two_bumps() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut path = temp_dir.path().join("deploy_v1.0.0.tskln");
+        fs::write(&path, "@Taskline version v1.0.0\n").unwrap();
+        for _ in 0..2 {
+            let args = Args::parse_from(["bump", path.to_str().unwrap(), "--fmt", "..x", "--link-latest", "--no-update-references"]);
+            path = bump_file(&args, &path, BumpType::Patch).unwrap().path;
+        }
+        let link = temp_dir.path().join("deploy.latest.tskln");
+        assert_eq!(fs::read_to_string(&link).unwrap(), "@Taskline version v1.0.2\n");
+        #[cfg(unix)]
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("deploy_v1.0.2.tskln"));
+    }
+
+    /// Bump `content` through scan_header/write_bumped and return the new file's bytes
+    fn bump_bytes(content: &[u8], version_line: &str) -> Vec<u8> {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("script.tskln");
+        let dst = temp_dir.path().join("script_v9.9.9.tskln");
+        fs::write(&src, content).unwrap();
+        let scan = scan_header(BufReader::new(fs::File::open(&src).unwrap())).unwrap();
+        write_bumped(&src, &dst, &scan, version_line).unwrap();
+        assert!(!src.exists());
+        assert!(!temp_path_for(&dst).exists());
+        fs::read(&dst).unwrap()
+    }
+
+    #[test]
+    fn test_scan_header_stops_at_body() {
+        let content = b"@Taskline codename s\n@Taskline version 1.2.3\n\nbody\n";
+        let scan = scan_header(&content[..]).unwrap();
+        assert_eq!(scan.version, Some((1, 2, 3)));
+        assert_eq!(scan.span, 21..44);
+
+        // Comments among the directives do not end the header
+        let scan = scan_header(&b"@Taskline codename s\n// owner: ops\n@Taskline version v1.2.3\n"[..]).unwrap();
+        assert_eq!(scan.version, Some((1, 2, 3)));
+
+        // A version line in the body is not part of the header
+        let scan = scan_header(&b"@Taskline codename s\n\n@Taskline version 1.2.3\n"[..]).unwrap();
+        assert_eq!(scan.version, None);
+        assert_eq!(scan.span, 21..21);
+    }
+
+    #[test]
+    fn test_write_bumped_preserves_body_bytes() {
+        let mut body = b"\n-> task\r\nbinary \xff\xfe tail without newline".to_vec();
+        let mut content = b"@Taskline codename s\n@Taskline version 1.2.3\n".to_vec();
+        content.append(&mut body.clone());
+
+        // Same length: patched in place
+        let bumped = bump_bytes(&content, "@Taskline version 1.2.4");
+        assert_eq!(bumped, [&b"@Taskline codename s\n@Taskline version 1.2.4\n"[..], &body].concat());
+
+        // Longer line: body shifted, still byte-identical
+        let bumped = bump_bytes(&content, "@Taskline version 1.10.0");
+        let mut expected = b"@Taskline codename s\n@Taskline version 1.10.0\n".to_vec();
+        expected.append(&mut body);
+        assert_eq!(bumped, expected);
+    }
+
+    #[test]
+    fn test_write_bumped_inserts_missing_version() {
+        assert_eq!(
+            bump_bytes(b"@Taskline codename s\n\nbody", "@Taskline version 0.0.1"),
+            b"@Taskline codename s\n@Taskline version 0.0.1\n\nbody"
+        );
+        assert_eq!(
+            bump_bytes(b"@Taskline codename s", "@Taskline version 0.0.1"),
+            b"@Taskline codename s\n@Taskline version 0.0.1"
+        );
+        assert_eq!(
+            bump_bytes(b"@Taskline codename s\r\n\r\nbody\r\n", "@Taskline version 0.0.1"),
+            b"@Taskline codename s\r\n@Taskline version 0.0.1\r\n\r\nbody\r\n"
+        );
+    }
+
+    #[test]
+    fn test_bump_frontmatter() {
+        let content = b"---\ncodename: s\nversion: 'v1.2.3'\nnotes:\n  version: 9.9.9\n---\nbody\n";
+        let scan = scan_header(&content[..]).unwrap();
+        assert!(scan.frontmatter);
+        assert_eq!(scan.version, Some((1, 2, 3)));
+        assert_eq!(
+            bump_bytes(content, "version: v1.2.4"),
+            b"---\ncodename: s\nversion: v1.2.4\nnotes:\n  version: 9.9.9\n---\nbody\n"
+        );
+
+        // Missing version goes right after the opening delimiter
+        let scan = scan_header(&b"---\ncodename: s\n---\nversion: 1.0.0\n"[..]).unwrap();
+        assert_eq!((scan.version, scan.span, scan.frontmatter), (None, 4..4, true));
+    }
+
+    #[test]
+    fn test_round_trip_with_core() {
+        // A header as taskline-init writes it (canonical `v` form) ...
+        let mut meta = taskline_core::TasklineMetadata::parse("@Taskline codename deploy\n");
+        meta.version = Some(Version::new(1, 4, 9));
+        let content = format!("{}body\n", meta.to_header());
+        let scan = scan_header(content.as_bytes()).unwrap();
+        assert_eq!(scan.version, Some((1, 4, 9)));
+
+        // ... bumps to a header the core parser reads back
+        let bumped = String::from_utf8(bump_bytes(content.as_bytes(), &format!("@Taskline version {}", Version::new(1, 4, 10)))).unwrap();
+        let parsed = taskline_core::TasklineMetadata::validate(&bumped).unwrap();
+        assert_eq!(parsed.version, Some(Version::new(1, 4, 10)));
+        assert!(bumped.contains("@Taskline version v1.4.10\n"));
+
+        // Legacy unprefixed headers are understood by both
+        let legacy = "@Taskline codename deploy\n@Taskline version 2.0.0\n";
+        assert_eq!(scan_header(legacy.as_bytes()).unwrap().version, Some((2, 0, 0)));
+        assert_eq!(taskline_core::TasklineMetadata::parse(legacy).version, Some(Version::new(2, 0, 0)));
+    }
+
     #[tokio::test]
     async fn test_version_line_insertion_logic() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,217 @@
+// File: taskline-bump/src/release.rs
+// --- `--release`: bump, CHANGELOG.md entry, commit and tag in one step, optionally pushed
+// --- Everything that can refuse is checked before the first change
+
+use std::error::Error;
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use taskline_core::Version;
+use crate::conventional::{self, git};
+use crate::{bump_file, choose_bump_type, next_version, scan_header, Args, BumpType};
+
+/// Changelog kept next to the released script
+const CHANGELOG: &str = "CHANGELOG.md";
+
+/// Release one script. The working tree must have no uncommitted changes to tracked files,
+/// so the release commit holds only what the bump, the changelog and the hooks changed.
+pub fn release(args: &Args, path: &Path) -> Result<(), Box<dyn Error>> {
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let name = path.file_name().and_then(|n| n.to_str()).ok_or("the script path has no file name")?;
+    let base = taskline_core::versioned::parse_file_name(name)
+        .map(|parsed| parsed.base)
+        .unwrap_or_else(|| name.split('.').next().unwrap_or(name));
+
+    let status = run_git(dir, &["status", "--porcelain", "--untracked-files=no"])?;
+    if !status.trim().is_empty() {
+        return Err("The working tree has uncommitted changes; commit or stash them before releasing".into());
+    }
+
+    // The changelog lists the commits since the previous release, as `--auto` reads them
+    let scan = scan_header(BufReader::new(fs::File::open(path)?))?;
+    let (major, minor, patch) = scan.version.unwrap_or((0, 0, 0));
+    let previous = conventional::version_tag(dir, base, &Version::new(major, minor, patch).to_string())?;
+    let messages = conventional::commit_messages(path, previous.as_deref())?;
+
+    let bump_type = choose_bump_type(args, path, previous.as_deref())?;
+    let (major, minor, patch) = next_version((major, minor, patch), bump_type)?;
+    let version = Version::new(major, minor, patch).to_string();
+    let tag = format!("{}-{}", base, version);
+    let title = format!("Release {} {}", base, version);
+    if git(dir).args(["rev-parse", "--verify", "--quiet"]).arg(format!("refs/tags/{}", tag)).output()?.status.success() {
+        return Err(format!("Tag {} already exists", tag).into());
+    }
+
+    let bumped = bump_file(args, path, bump_type)?;
+
+    let changelog = dir.join(CHANGELOG);
+    let entry = changelog_entry(base, &version, &today(), messages.iter().map(String::as_str));
+    if args.dry_run {
+        println!("Would add to {}:", changelog.display());
+        for line in entry.lines() {
+            println!("    {}", line);
+        }
+        println!("Would commit \"{}\" and tag {}", title, tag);
+        if args.push {
+            println!("Would push the commit and {} to origin", tag);
+        }
+        return Ok(());
+    }
+
+    let existing = match fs::read_to_string(&changelog) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("{}: {}", changelog.display(), e).into()),
+    };
+    fs::write(&changelog, insert_entry(&existing, &entry))?;
+    println!("Updated {}", changelog.display());
+
+    // `-u` picks up the old name's removal and rewritten references; the rest is new
+    run_git(dir, &["add", "-u"])?;
+    run_git(dir, &["add", "--", bumped.path.file_name().and_then(|n| n.to_str()).unwrap_or(name), CHANGELOG])?;
+    run_git(dir, &["commit", "--quiet", "-m", &title])?;
+    run_git(dir, &["tag", "-a", &tag, "-m", &title])?;
+    println!("Committed \"{}\" and tagged {}", title, tag);
+
+    if args.push {
+        run_git(dir, &["push", "--atomic", "origin", "HEAD", &format!("refs/tags/{}", tag)])?;
+        println!("Pushed the commit and {} to origin", tag);
+    }
+    Ok(())
+}
+
+/// Run git from `dir`, returning its output or its error message
+fn run_git(dir: &Path, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = git(dir).args(args).output().map_err(|e| format!("git could not be started: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Markdown for one release, with the conventional commits grouped by the level they ask
+/// for; other commits are left out
+fn changelog_entry<'a>(base: &str, version: &str, date: &str, messages: impl IntoIterator<Item = &'a str>) -> String {
+    let mut sections: [(&str, Vec<&str>); 3] = [("Breaking Changes", Vec::new()), ("Features", Vec::new()), ("Fixes", Vec::new())];
+    for message in messages {
+        let (Some(level), Some(description)) = (conventional::classify(message), conventional::description(message)) else { continue };
+        let section = match level {
+            BumpType::Major => 0,
+            BumpType::Minor => 1,
+            BumpType::Patch => 2,
+        };
+        sections[section].1.push(description);
+    }
+
+    let mut entry = format!("## {} {} ({})\n", base, version, date);
+    if sections.iter().all(|(_, items)| items.is_empty()) {
+        entry.push_str("\nNo notable changes.\n");
+    }
+    for (heading, items) in sections.iter().filter(|(_, items)| !items.is_empty()) {
+        entry.push_str(&format!("\n### {}\n\n", heading));
+        for item in items {
+            entry.push_str(&format!("- {}\n", item));
+        }
+    }
+    entry
+}
+
+/// Put `entry` above the previous releases, below a leading `# ` title; a new changelog gets one
+fn insert_entry(existing: &str, entry: &str) -> String {
+    if existing.trim().is_empty() {
+        return format!("# Changelog\n\n{}", entry);
+    }
+    match existing.strip_prefix("# ").and(existing.split_once('\n')) {
+        Some((title, rest)) => format!("{}\n\n{}\n{}", title, entry, rest.trim_start_matches('\n')),
+        None => format!("{}\n{}", entry, existing),
+    }
+}
+
+/// Today's UTC date as `YYYY-MM-DD`
+fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    date_from_days((seconds / 86_400) as i64)
+}
+
+/// Civil date of a day count since 1970-01-01 (Howard Hinnant's `civil_from_days`)
+fn date_from_days(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changelog_entry() {
+        let messages = ["fix: quote paths", "docs: typo", "feat(cli): add --dry-run", "feat!: drop --legacy", "Merge branch 'x'"];
+        assert_eq!(
+            changelog_entry("deploy", "v2.0.0", "2026-10-16", messages),
+            "## deploy v2.0.0 (2026-10-16)\n\n### Breaking Changes\n\n- drop --legacy\n\n### Features\n\n- add --dry-run\n\n### Fixes\n\n- quote paths\n"
+        );
+        assert_eq!(changelog_entry("deploy", "v1.0.1", "2026-10-16", ["chore: deps"]), "## deploy v1.0.1 (2026-10-16)\n\nNo notable changes.\n");
+    }
+
+    #[test]
+    fn test_insert_entry() {
+        let entry = "## a v1.1.0 (2026-10-16)\n\n- new\n";
+        assert_eq!(insert_entry("", entry), "# Changelog\n\n## a v1.1.0 (2026-10-16)\n\n- new\n");
+        assert_eq!(
+            insert_entry("# Changelog\n\n## a v1.0.0 (2026-01-01)\n", entry),
+            "# Changelog\n\n## a v1.1.0 (2026-10-16)\n\n- new\n\n## a v1.0.0 (2026-01-01)\n"
+        );
+        assert_eq!(insert_entry("## a v1.0.0\n", entry), "## a v1.1.0 (2026-10-16)\n\n- new\n\n## a v1.0.0\n");
+    }
+
+    #[test]
+    fn test_consecutive_releases() {
+        use clap::Parser;
+        let dir = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| git(dir.path()).args(args).output().is_ok_and(|output| output.status.success());
+        if !run(&["init", "--quiet"]) {
+            return; // git is not installed
+        }
+        assert!(run(&["config", "user.name", "Test"]) && run(&["config", "user.email", "test@example.com"]));
+        assert!(run(&["config", "commit.gpgsign", "false"]) && run(&["config", "tag.gpgsign", "false"]));
+        fs::write(dir.path().join("deploy_v1.0.0.tskln"), "@Taskline version v1.0.0\n").unwrap();
+        assert!(run(&["add", "deploy_v1.0.0.tskln"]) && run(&["commit", "--quiet", "-m", "feat: first version"]));
+
+        for (from, to) in [("v1.0.0", "v1.0.1"), ("v1.0.1", "v1.0.2")] {
+            let path = dir.path().join(format!("deploy_{}.tskln", from));
+            let args = Args::parse_from(["bump", path.to_str().unwrap(), "--fmt", "..x", "--release", "--no-update-references"]);
+            release(&args, &path).unwrap();
+            assert!(!path.exists());
+            assert!(dir.path().join(format!("deploy_{}.tskln", to)).exists());
+            assert!(run(&["rev-parse", "--verify", "--quiet", &format!("refs/tags/deploy-{}", to)]));
+        }
+
+        let changelog = fs::read_to_string(dir.path().join(CHANGELOG)).unwrap();
+        let headings: Vec<_> = changelog.lines().filter(|line| line.starts_with("## ")).map(|line| &line[..line.find(" (").unwrap()]).collect();
+        assert_eq!(headings, ["## deploy v1.0.2", "## deploy v1.0.1"]);
+        let files = run_git(dir.path(), &["ls-files"]).unwrap();
+        assert_eq!(files.lines().collect::<Vec<_>>(), [CHANGELOG, "deploy_v1.0.2.tskln"]);
+        let subject = run_git(dir.path(), &["log", "-1", "--format=%s"]).unwrap();
+        assert_eq!(subject.trim(), "Release deploy v1.0.2");
+    }
+
+    #[test]
+    fn test_date_from_days() {
+        assert_eq!(date_from_days(0), "1970-01-01");
+        assert_eq!(date_from_days(11_016), "2000-02-29");
+        assert_eq!(date_from_days(20_742), "2026-10-16");
+        assert_eq!(date_from_days(-1), "1969-12-31");
+    }
+}
@@ -0,0 +1,45 @@
+// File: taskline-core/benches/header_scan.rs
+// --- Header-only scanning versus reading and parsing whole scripts
+// --- Run with `cargo bench -p taskline-core`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use taskline_core::{scan, TasklineMetadata};
+
+const HEADER: &str = "// Deploy pipeline\n@Taskline codename deploy\n@Taskline version v2.4.1\n@Taskline author Jane Doe <jane@example.com>\n@Taskline created 2025-01-31\n@Taskline requires node >= 18, docker\n\n";
+
+fn script(body_lines: usize) -> String {
+    let mut script = HEADER.to_string();
+    for i in 0..body_lines {
+        script.push_str(&format!("echo \"step {}\" && ./run --flag value-{}\n", i, i));
+    }
+    script
+}
+
+fn bench_header_len(c: &mut Criterion) {
+    let mut group = c.benchmark_group("header_len");
+    for lines in [100, 100_000] {
+        let content = script(lines);
+        group.bench_function(format!("split_header/{}", lines), |b| b.iter(|| taskline_core::split_header(black_box(&content)).0.len()));
+        group.bench_function(format!("lines_filter/{}", lines), |b| {
+            b.iter(|| black_box(&content).lines().filter(|line| line.starts_with("@Taskline ")).count())
+        });
+    }
+    group.finish();
+}
+
+fn bench_read_metadata(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut group = c.benchmark_group("read_metadata");
+    for lines in [100, 100_000] {
+        let path = dir.path().join(format!("deploy-{}.tskln", lines));
+        std::fs::write(&path, script(lines)).unwrap();
+        group.bench_function(format!("read_to_string/{}", lines), |b| {
+            b.iter(|| TasklineMetadata::validate(&std::fs::read_to_string(&path).unwrap()).unwrap())
+        });
+        group.bench_function(format!("scan/{}", lines), |b| b.iter(|| scan::read_metadata(&path).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_header_len, bench_read_metadata);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+// File: taskline-core/src/author.rs
+// --- `@Taskline author Name <email>` entries; a script may list several
+// --- The email is optional so a bare name (or team name) is still an author
+
+//! Script authors and maintainers.
+
+use crate::TasklineError;
+
+/// One `@Taskline author` entry
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[serde(deny_unknown_fields)]
+pub struct Author {
+    /// Display name
+    pub name: String,
+    /// Contact address from `<...>`, when given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl Author {
+    /// Create an author
+    pub fn new(name: impl Into<String>, email: Option<String>) -> Self {
+        Self { name: name.into(), email }
+    }
+
+    /// Parse `Name <email>`, `Name` or `<email>`
+    pub fn parse(value: &str) -> Result<Self, TasklineError> {
+        let invalid = |reason: &str| TasklineError::ParseError(format!("Invalid author '{}': {}", value, reason));
+        let value = value.trim();
+        let (name, email) = match value.find('<') {
+            Some(open) => {
+                let rest = &value[open + 1..];
+                let close = rest.find('>').ok_or_else(|| invalid("missing '>'"))?;
+                if !rest[close + 1..].trim().is_empty() {
+                    return Err(invalid("unexpected text after '>'"));
+                }
+                let email = rest[..close].trim();
+                if email.is_empty() || !email.contains('@') || email.contains(char::is_whitespace) {
+                    return Err(invalid("malformed email address"));
+                }
+                (value[..open].trim(), Some(email.to_string()))
+            }
+            None if value.contains('>') => return Err(invalid("missing '<'")),
+            None => (value, None),
+        };
+        if name.is_empty() && email.is_none() {
+            return Err(invalid("it is empty"));
+        }
+        Ok(Self::new(name, email))
+    }
+}
+
+impl std::fmt::Display for Author {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.email, self.name.is_empty()) {
+            (Some(email), true) => write!(f, "<{}>", email),
+            (Some(email), false) => write!(f, "{} <{}>", self.name, email),
+            (None, _) => f.write_str(&self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let author = Author::parse("Jane Doe <jane@example.com>").unwrap();
+        assert_eq!(author, Author::new("Jane Doe", Some("jane@example.com".to_string())));
+        assert_eq!(author.to_string(), "Jane Doe <jane@example.com>");
+        assert_eq!(Author::parse("  Platform Team ").unwrap().to_string(), "Platform Team");
+        assert_eq!(Author::parse("<ops@example.com>").unwrap().name, "");
+
+        assert!(Author::parse("").is_err());
+        assert!(Author::parse("Jane <jane@example.com").is_err());
+        assert!(Author::parse("Jane <not an email>").is_err());
+        assert!(Author::parse("Jane <jane@example.com> extra").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+// File: taskline-core/src/ci.rs
+// --- CI environment detection shared by all Taskline tools
+// --- CI mode means: no prompts, no emoji/colors, stable output
+
+//! CI mode detection.
+//!
+//! The dispatcher, `taskline-init` and `taskline-bump` all call [`detect`]. The dispatcher
+//! also exports [`CI_ENV_VAR`] to the components it runs, so `taskline --ci` reaches them.
+
+/// Set by the dispatcher for child components; `0`/`false` forces CI mode off
+pub const CI_ENV_VAR: &str = "TASKLINE_CI";
+
+/// Variables exported by common CI providers
+const PROVIDER_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "TF_BUILD",
+    "JENKINS_URL",
+    "TEAMCITY_VERSION",
+];
+
+/// Detect CI mode from the process environment
+pub fn detect() -> bool {
+    detect_with(|key| std::env::var(key).ok())
+}
+
+/// Detect CI mode using a custom environment lookup
+pub fn detect_with<F: Fn(&str) -> Option<String>>(lookup: F) -> bool {
+    if let Some(value) = lookup(CI_ENV_VAR) {
+        return is_truthy(&value);
+    }
+    PROVIDER_VARS.iter().any(|key| lookup(key).is_some_and(|v| is_truthy(&v)))
+}
+
+#[inline]
+fn is_truthy(value: &str) -> bool {
+    !matches!(value.trim().to_ascii_lowercase().as_str(), "" | "0" | "false" | "no")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+    }
+
+    #[test]
+    fn test_detect_providers() {
+        assert!(!detect_with(env(&[])));
+        assert!(detect_with(env(&[("CI", "true")])));
+        assert!(detect_with(env(&[("GITHUB_ACTIONS", "true")])));
+        assert!(!detect_with(env(&[("CI", "false")])));
+        assert!(!detect_with(env(&[("CI", "")])));
+    }
+
+    #[test]
+    fn test_taskline_override() {
+        assert!(detect_with(env(&[(CI_ENV_VAR, "1")])));
+        assert!(!detect_with(env(&[(CI_ENV_VAR, "0"), ("CI", "true")])));
+    }
+}
@@ -0,0 +1,138 @@
+// File: taskline-core/src/codename.rs
+// --- Script codenames: the one place that decides what may become part of a file name
+// --- ASCII letters, digits, '-' and '_', starting with a letter or digit, at most 64 bytes
+
+//! Validated script codenames.
+
+use crate::TasklineError;
+
+/// A script codename that is safe to use as a file or directory name on every platform
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Codename(String);
+
+impl Codename {
+    /// Longest accepted codename, in bytes
+    pub const MAX_LEN: usize = 64;
+
+    /// Validate a codename, rejecting anything that is not already in canonical form
+    pub fn parse(name: &str) -> Result<Self, TasklineError> {
+        let invalid = |reason: &str| TasklineError::ParseError(format!("Invalid codename '{}': {}", name, reason));
+        if name.is_empty() {
+            return Err(invalid("it is empty"));
+        }
+        if name.len() > Self::MAX_LEN {
+            return Err(invalid(&format!("longer than {} characters", Self::MAX_LEN)));
+        }
+        if !name.bytes().all(is_allowed) {
+            return Err(invalid("only letters, digits, '-' and '_' are allowed"));
+        }
+        if !name.as_bytes()[0].is_ascii_alphanumeric() {
+            return Err(invalid("it must start with a letter or digit"));
+        }
+        if is_reserved(name) {
+            return Err(invalid("it is a reserved device name on Windows"));
+        }
+        Ok(Self(name.to_string()))
+    }
+
+    /// Turn arbitrary text into a valid codename: disallowed characters become `-`, runs of
+    /// separators collapse, and the result is trimmed to [`Codename::MAX_LEN`]. Fails only
+    /// when nothing usable is left.
+    pub fn sanitize(name: &str) -> Result<Self, TasklineError> {
+        let mut sanitized = String::with_capacity(name.len().min(Self::MAX_LEN));
+        for c in name.chars() {
+            let c = if c.is_ascii() && is_allowed(c as u8) { c } else { '-' };
+            let separator = c == '-' || c == '_';
+            if separator && (sanitized.is_empty() || sanitized.ends_with(['-', '_'])) {
+                continue;
+            }
+            sanitized.push(c);
+        }
+        sanitized.truncate(Self::MAX_LEN);
+        let trimmed = sanitized.trim_end_matches(['-', '_']);
+        if is_reserved(trimmed) {
+            return Self::parse(&format!("{}_", trimmed));
+        }
+        Self::parse(trimmed)
+    }
+
+    /// The codename as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn is_allowed(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'
+}
+
+/// `CON`, `NUL`, `COM1`... cannot be file names on Windows, whatever the extension
+fn is_reserved(name: &str) -> bool {
+    const RESERVED: [&str; 4] = ["CON", "PRN", "AUX", "NUL"];
+    let upper = name.to_ascii_uppercase();
+    RESERVED.contains(&upper.as_str())
+        || ((upper.starts_with("COM") || upper.starts_with("LPT"))
+            && upper.len() == 4
+            && matches!(upper.as_bytes()[3], b'1'..=b'9'))
+}
+
+impl std::fmt::Display for Codename {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Codename {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for Codename {
+    type Err = TasklineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Always a valid codename, so fuzz targets get past validation
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Codename {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let name: String = u.arbitrary()?;
+        Ok(Self::sanitize(&name).unwrap_or_else(|_| Self("script".to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Codename::parse("deploy-prod_2").unwrap().as_str(), "deploy-prod_2");
+        assert!(Codename::parse("").is_err());
+        assert!(Codename::parse("../../etc/passwd").is_err());
+        assert!(Codename::parse("a/b").is_err());
+        assert!(Codename::parse("a\\b").is_err());
+        assert!(Codename::parse(".hidden").is_err());
+        assert!(Codename::parse("-flag").is_err());
+        assert!(Codename::parse("name.v1").is_err());
+        assert!(Codename::parse("nul").is_err());
+        assert!(Codename::parse("com1").is_err());
+        assert!(Codename::parse("com").is_ok());
+        assert!(Codename::parse(&"a".repeat(Codename::MAX_LEN)).is_ok());
+        assert!(Codename::parse(&"a".repeat(Codename::MAX_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(Codename::sanitize("../../etc/passwd").unwrap().as_str(), "etc-passwd");
+        assert_eq!(Codename::sanitize("My Deploy Script!").unwrap().as_str(), "My-Deploy-Script");
+        assert_eq!(Codename::sanitize("café").unwrap().as_str(), "caf");
+        assert_eq!(Codename::sanitize("CON").unwrap().as_str(), "CON_");
+        assert_eq!(Codename::sanitize(&"x".repeat(100)).unwrap().as_str().len(), Codename::MAX_LEN);
+        assert!(Codename::sanitize("../..").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+// File: taskline-core/src/config.rs
+// --- Repository-level `.taskline.toml` configuration
+// --- Discovered by walking up from a script's directory
+
+//! Repository configuration (`.taskline.toml`).
+
+use std::path::PathBuf;
+#[cfg(feature = "fs")]
+use std::path::Path;
+use serde::Deserialize;
+use crate::naming::NamingConfig;
+use crate::TasklineError;
+
+/// Name of the repository-level configuration file
+pub const CONFIG_FILE_NAME: &str = ".taskline.toml";
+
+/// Parsed `.taskline.toml` contents
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TasklineConfig {
+    /// `[hooks]` section
+    pub hooks: HooksConfig,
+    /// `[bump]` section
+    pub bump: BumpConfig,
+    /// `[registry]` section
+    pub registry: Option<RegistryConfig>,
+    /// `[naming]` section
+    pub naming: NamingConfig,
+}
+
+/// Commands run around version bumps, executed through the platform shell
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Run before the file is touched; any failure aborts the bump
+    #[serde(rename = "pre-bump")]
+    pub pre_bump: Vec<String>,
+    /// Run after the bumped file has been written and renamed
+    #[serde(rename = "post-bump")]
+    pub post_bump: Vec<String>,
+}
+
+/// Options of `taskline-bump`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BumpConfig {
+    /// Keep `<name>.latest.<ext>` pointing at the newest version after every bump
+    #[serde(rename = "link-latest")]
+    pub link_latest: bool,
+}
+
+/// Script registry location used by `taskline publish` / `taskline pull`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// Registry directory, relative to the config file
+    pub path: Option<PathBuf>,
+    /// Git repository URL
+    pub git: Option<String>,
+    /// Branch of the git repository (defaults to the remote HEAD)
+    pub branch: Option<String>,
+}
+
+/// A configuration file together with the directory it was found in
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    /// Directory containing the config file
+    pub root: PathBuf,
+    /// Parsed contents
+    pub config: TasklineConfig,
+}
+
+impl TasklineConfig {
+    /// Parse configuration from TOML text
+    pub fn parse(content: &str) -> Result<Self, TasklineError> {
+        let config: Self = toml::from_str(content).map_err(|e| TasklineError::ConfigError(e.to_string()))?;
+        config.naming.validate()?;
+        Ok(config)
+    }
+
+    /// Load configuration from an explicit file path
+    #[cfg(feature = "fs")]
+    pub fn load(path: &Path) -> Result<Self, TasklineError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| TasklineError::FileError(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&content)
+            .map_err(|e| TasklineError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Find the nearest `.taskline.toml` at or above `start` and load it
+    #[cfg(feature = "fs")]
+    pub fn discover(start: &Path) -> Result<Option<LoadedConfig>, TasklineError> {
+        let mut dir = Some(start);
+        while let Some(current) = dir {
+            let candidate = current.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                let config = Self::load(&candidate)?;
+                return Ok(Some(LoadedConfig { root: current.to_path_buf(), config }));
+            }
+            dir = current.parent();
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hooks() {
+        let config = TasklineConfig::parse(
+            "[hooks]\npre-bump = [\"cargo test\"]\npost-bump = [\"git tag $TASKLINE_NEW_VERSION\", \"git push --tags\"]\n",
+        ).unwrap();
+        assert_eq!(config.hooks.pre_bump, vec!["cargo test"]);
+        assert_eq!(config.hooks.post_bump.len(), 2);
+        assert!(!config.bump.link_latest);
+        assert!(TasklineConfig::parse("[bump]\nlink-latest = true\n").unwrap().bump.link_latest);
+    }
+
+    #[test]
+    fn test_parse_empty_and_invalid() {
+        let config = TasklineConfig::parse("").unwrap();
+        assert!(config.hooks.pre_bump.is_empty());
+        assert!(config.hooks.post_bump.is_empty());
+        assert!(config.registry.is_none());
+
+        assert!(TasklineConfig::parse("[hooks]\npre-bump = \"not a list\"").is_err());
+        assert!(TasklineConfig::parse("[hooks]\nbefore = []").is_err()); // Unknown key
+    }
+
+    #[test]
+    fn test_parse_registry() {
+        let config = TasklineConfig::parse("[registry]\ngit = \"git@example.com:org/scripts.git\"\nbranch = \"main\"\n").unwrap();
+        let registry = config.registry.unwrap();
+        assert_eq!(registry.git.as_deref(), Some("git@example.com:org/scripts.git"));
+        assert_eq!(registry.branch.as_deref(), Some("main"));
+        assert!(registry.path.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_discover_walks_up() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let nested = root.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(root.join(CONFIG_FILE_NAME), "[hooks]\npre-bump = [\"true\"]\n").unwrap();
+
+        let loaded = TasklineConfig::discover(&nested).unwrap().unwrap();
+        assert_eq!(loaded.root, root);
+        assert_eq!(loaded.config.hooks.pre_bump, vec!["true"]);
+    }
+}
@@ -0,0 +1,125 @@
+// File: taskline-core/src/executable.rs
+// --- Portable `which`: resolve a program name against PATH without shelling out
+// --- On Windows the name may omit any PATHEXT extension (`.exe`, `.cmd`, ...)
+
+//! Executable lookup on `PATH`.
+//!
+//! [`find`] resolves a program the way the platform shell would. On Unix a candidate
+//! must be a file with an execute bit. On Windows every file counts, and a name without
+//! a `PATHEXT` extension is tried with each one in order, so `taskline-bump` finds
+//! `taskline-bump.exe` and `npm` finds `npm.cmd`.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried on Windows when `PATHEXT` is unset
+pub const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Full path of the program `name` on the current `PATH`. A name with a directory part
+/// is checked as given instead of being looked up.
+pub fn find(name: &str) -> Option<PathBuf> {
+    find_on(name, &std::env::var_os("PATH").unwrap_or_default())
+}
+
+/// Like [`find`], but searching `path` (a `PATH`-style list) instead of the current
+/// `PATH`, e.g. one with a script's `@Taskline path` entries prepended
+pub fn find_on(name: &str, path: &OsStr) -> Option<PathBuf> {
+    if cfg!(windows) {
+        let pathext = std::env::var_os("PATHEXT").unwrap_or_else(|| DEFAULT_PATHEXT.into());
+        find_in(name, path, Some(&pathext))
+    } else {
+        find_in(name, path, None)
+    }
+}
+
+/// Search the directories of `path` (a `PATH`-style list) for `name`. With `extensions`
+/// (a `PATHEXT`-style list) lookup follows the Windows rules, otherwise the Unix ones.
+pub fn find_in(name: &str, path: &OsStr, extensions: Option<&OsStr>) -> Option<PathBuf> {
+    let candidates = candidates(name, extensions);
+    let found = |candidate: &PathBuf| is_executable(candidate, extensions.is_none());
+    if Path::new(name).components().count() > 1 {
+        return candidates.into_iter().map(PathBuf::from).find(found);
+    }
+    // An empty entry would mean the current directory, which is not searched
+    std::env::split_paths(path)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .find_map(|dir| candidates.iter().map(|candidate| dir.join(candidate)).find(found))
+}
+
+/// `name` as is when it already ends in one of `extensions`, otherwise with each of them
+fn candidates(name: &str, extensions: Option<&OsStr>) -> Vec<String> {
+    let Some(extensions) = extensions else { return vec![name.to_string()] };
+    let extensions = extensions.to_string_lossy();
+    let extensions: Vec<String> = extensions
+        .split(';')
+        .map(str::trim)
+        .filter(|ext| ext.starts_with('.') && ext.len() > 1)
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let lower = name.to_ascii_lowercase();
+    if extensions.iter().any(|ext| lower.ends_with(ext.as_str())) {
+        return vec![name.to_string()];
+    }
+    extensions.iter().map(|ext| format!("{}{}", name, ext)).collect()
+}
+
+/// A regular file that, under Unix rules, has an execute bit set
+fn is_executable(path: &Path, unix_rules: bool) -> bool {
+    let Ok(metadata) = path.metadata() else { return false };
+    if !metadata.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    if unix_rules {
+        use std::os::unix::fs::PermissionsExt;
+        return metadata.permissions().mode() & 0o111 != 0;
+    }
+    let _ = unix_rules;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let pathext = Some(OsStr::new(".COM;.EXE;;.CMD"));
+        assert_eq!(candidates("taskline-bump", pathext), ["taskline-bump.com", "taskline-bump.exe", "taskline-bump.cmd"]);
+        assert_eq!(candidates("npm.CMD", pathext), ["npm.CMD"]);
+        assert_eq!(candidates("archive.tar", pathext), ["archive.tar.com", "archive.tar.exe", "archive.tar.cmd"]);
+        assert_eq!(candidates("taskline-bump", None), ["taskline-bump"]);
+    }
+
+    #[test]
+    fn test_find_in() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let create = |dir: &Path, name: &str, mode: u32| {
+            let file = dir.join(name);
+            std::fs::write(&file, "").unwrap();
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&file, std::fs::Permissions::from_mode(mode)).unwrap();
+            }
+            let _ = mode;
+            file
+        };
+        create(first.path(), "tool", 0o644);
+        let tool = create(second.path(), "tool", 0o755);
+        let bump = create(second.path(), "taskline-bump.exe", 0o644);
+        std::fs::create_dir(first.path().join("taskline-bump.com")).unwrap();
+        let path = std::env::join_paths([first.path(), second.path()]).unwrap();
+        let pathext = Some(OsStr::new(".COM;.EXE"));
+
+        #[cfg(unix)]
+        assert_eq!(find_in("tool", &path, None), Some(tool.clone()));
+        assert_eq!(find_in("taskline-bump", &path, pathext), Some(bump.clone()));
+        assert_eq!(find_in("taskline-bump", &path, None), None);
+        assert_eq!(find_in("missing", &path, pathext), None);
+        assert_eq!(find_in(tool.to_str().unwrap(), OsStr::new(""), None), Some(tool));
+        let without_extension = second.path().join("taskline-bump");
+        assert_eq!(find_in(without_extension.to_str().unwrap(), OsStr::new(""), pathext), Some(bump));
+    }
+}
@@ -0,0 +1,241 @@
+// File: taskline-core/src/frontmatter.rs
+// --- Opt-in YAML frontmatter (`--- ... ---`) as an alternative to `@Taskline` lines
+// --- Parsed into the same TasklineMetadata; keys it does not model are kept for conversion
+
+//! YAML frontmatter headers (feature `yaml`).
+//!
+//! ```text
+//! ---
+//! codename: deploy
+//! version: v1.2.0
+//! requires:
+//!   - node >= 18
+//!   - docker
+//! ---
+//! ```
+//!
+//! `path` and `requires` accept a single string or a list. Other keys, including nested
+//! ones, are allowed and ignored by [`TasklineMetadata`].
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::{split_header, Author, LineEnding, TasklineError, TasklineMetadata, Version};
+
+/// Opening and closing line of a frontmatter block
+pub const DELIMITER: &str = "---";
+
+/// Split content into its frontmatter YAML (without the delimiters) and the rest, or
+/// `None` when the content does not start with a complete frontmatter block
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next()?;
+    if first.trim_end_matches(['\n', '\r']) != DELIMITER {
+        return None;
+    }
+    let start = first.len();
+    let mut end = start;
+    for line in lines {
+        if line.trim_end_matches(['\n', '\r']) == DELIMITER {
+            return Some((&content[start..end], &content[end + line.len()..]));
+        }
+        end += line.len();
+    }
+    None
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Frontmatter {
+    codename: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// `Name <email>` strings; `author` is accepted as a singular spelling
+    #[serde(default, alias = "author", deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    path: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    requires: Vec<String>,
+    #[serde(default, rename = "requires-taskline", skip_serializing_if = "Option::is_none")]
+    requires_taskline: Option<String>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+fn parse_yaml(yaml: &str) -> Result<Frontmatter, TasklineError> {
+    serde_yaml::from_str(yaml).map_err(|e| TasklineError::ParseError(format!("Invalid frontmatter: {}", e)))
+}
+
+fn to_metadata(frontmatter: &Frontmatter) -> Result<TasklineMetadata, TasklineError> {
+    Ok(TasklineMetadata {
+        codename: frontmatter.codename.clone(),
+        version: frontmatter.version.as_deref().map(Version::parse).transpose()?,
+        authors: frontmatter.authors.iter().map(|a| Author::parse(a)).collect::<Result<_, _>>()?,
+        created: frontmatter.created.clone(),
+        expires: frontmatter.expires.clone(),
+        license: frontmatter.license.clone(),
+        deprecated: frontmatter.deprecated.clone(),
+        path: frontmatter.path.clone(),
+        requires: frontmatter.requires.clone(),
+        requires_taskline: frontmatter.requires_taskline.clone(),
+    })
+}
+
+fn render(metadata: &TasklineMetadata, extra: BTreeMap<String, serde_yaml::Value>) -> Result<String, TasklineError> {
+    let frontmatter = Frontmatter {
+        codename: metadata.codename.clone(),
+        version: metadata.version.map(|v| v.to_string()),
+        authors: metadata.authors.iter().map(Author::to_string).collect(),
+        created: metadata.created.clone(),
+        expires: metadata.expires.clone(),
+        license: metadata.license.clone(),
+        deprecated: metadata.deprecated.clone(),
+        path: metadata.path.clone(),
+        requires: metadata.requires.clone(),
+        requires_taskline: metadata.requires_taskline.clone(),
+        extra,
+    };
+    let yaml = serde_yaml::to_string(&frontmatter)
+        .map_err(|e| TasklineError::ParseError(format!("Cannot render frontmatter: {}", e)))?;
+    Ok(format!("{}\n{}{}\n\n", DELIMITER, yaml, DELIMITER))
+}
+
+impl TasklineMetadata {
+    /// Parse the YAML of a frontmatter block (without its `---` lines)
+    pub fn from_frontmatter(yaml: &str) -> Result<Self, TasklineError> {
+        to_metadata(&parse_yaml(yaml)?)
+    }
+
+    /// Render as a frontmatter block, followed by a blank line like [`TasklineMetadata::to_header`]
+    pub fn to_frontmatter(&self) -> Result<String, TasklineError> {
+        render(self, BTreeMap::new())
+    }
+}
+
+/// Canonically re-render a script's frontmatter, keeping extra keys and the body.
+/// YAML comments inside the block do not survive the round trip.
+pub(crate) fn format_frontmatter(content: &str, yaml: &str, body: &str) -> Result<String, TasklineError> {
+    let frontmatter = parse_yaml(yaml)?;
+    let metadata = to_metadata(&frontmatter)?;
+    metadata.check_fields()?;
+    let rendered = render(&metadata, frontmatter.extra)?;
+    let ending = LineEnding::detect(content);
+    Ok(ending.apply(rendered.trim_end_matches('\n')) + ending.as_str() + body)
+}
+
+/// Convert a script with `@Taskline` lines to YAML frontmatter. Unknown directives
+/// become string keys; the body is kept as is.
+pub fn header_to_frontmatter(content: &str) -> Result<String, TasklineError> {
+    if split_frontmatter(content).is_some() {
+        return Err(TasklineError::ParseError("Script already uses frontmatter".to_string()));
+    }
+    let metadata = TasklineMetadata::validate(content)?;
+    let (header, body) = split_header(content);
+    let mut extra = BTreeMap::new();
+    for directive in header.lines().filter_map(|line| line.strip_prefix("@Taskline ")) {
+        let (key, value) = directive.split_once(' ').unwrap_or((directive, ""));
+        if !crate::is_known_directive(directive) {
+            extra.insert(key.to_string(), serde_yaml::Value::String(value.trim().to_string()));
+        }
+    }
+    Ok(LineEnding::detect(content).apply(&render(&metadata, extra)?) + body)
+}
+
+/// Convert a frontmatter script back to `@Taskline` lines. Extra keys with scalar values
+/// become directives; nested values have no line form and are an error.
+pub fn frontmatter_to_header(content: &str) -> Result<String, TasklineError> {
+    let (yaml, body) = split_frontmatter(content)
+        .ok_or_else(|| TasklineError::ParseError("Script has no frontmatter".to_string()))?;
+    let frontmatter = parse_yaml(yaml)?;
+    let metadata = to_metadata(&frontmatter)?;
+
+    let mut header = metadata.to_header();
+    header.pop();
+    for (key, value) in &frontmatter.extra {
+        let value = match value {
+            serde_yaml::Value::String(s) => s.clone(),
+            serde_yaml::Value::Number(n) => n.to_string(),
+            serde_yaml::Value::Bool(b) => b.to_string(),
+            _ => {
+                return Err(TasklineError::ParseError(format!(
+                    "Frontmatter key '{}' is not a plain value and has no @Taskline form", key
+                )))
+            }
+        };
+        header.push_str(&format!("@Taskline {} {}\n", key, value));
+    }
+    header.push('\n');
+    let body = body.trim_start_matches(['\r', '\n']);
+    Ok(LineEnding::detect(content).apply(&header) + body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = "---\ncodename: deploy\nversion: v1.2.0\nrequires:\n  - node >= 18\n  - docker\nowner: ops\n---\n\n-> body\n";
+
+    #[test]
+    fn test_split_frontmatter() {
+        let (yaml, body) = split_frontmatter(SCRIPT).unwrap();
+        assert!(yaml.starts_with("codename: deploy\n") && yaml.ends_with("owner: ops\n"));
+        assert_eq!(body, "\n-> body\n");
+        assert!(split_frontmatter("---\nunterminated: true\n").is_none());
+        assert!(split_frontmatter("@Taskline codename x\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_into_metadata() {
+        let metadata = TasklineMetadata::parse(SCRIPT);
+        assert_eq!(metadata.codename, "deploy");
+        assert_eq!(metadata.version, Some(Version::new(1, 2, 0)));
+        assert_eq!(metadata.requires, ["node >= 18", "docker"]);
+
+        let single = TasklineMetadata::from_frontmatter("codename: x\npath: bin\nnested:\n  a: [1, 2]\n").unwrap();
+        assert_eq!(single.path, ["bin"]);
+        assert!(TasklineMetadata::validate("---\ncodename: x\nversion: v1.0\n---\n").is_err());
+        assert!(TasklineMetadata::validate("---\nversion: v1.0.0\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_convert_both_ways() {
+        let header = frontmatter_to_header(SCRIPT).unwrap();
+        assert_eq!(
+            header,
+            "@Taskline codename deploy\n@Taskline version v1.2.0\n@Taskline requires node >= 18\n@Taskline requires docker\n@Taskline owner ops\n\n-> body\n"
+        );
+        let back = header_to_frontmatter(&header).unwrap();
+        assert_eq!(TasklineMetadata::parse(&back).requires, ["node >= 18", "docker"]);
+        assert!(back.contains("owner: ops\n---\n\n-> body\n"));
+        assert_eq!(frontmatter_to_header(&back).unwrap(), header);
+
+        assert!(frontmatter_to_header("---\ncodename: x\nnested:\n  a: 1\n---\n").is_err());
+        assert!(header_to_frontmatter(SCRIPT).is_err());
+    }
+
+    #[test]
+    fn test_format_frontmatter() {
+        let formatted = crate::format("---\r\nowner: ops\r\nversion: 1.0.0\r\ncodename: x\r\n---\r\nbody\r\n").unwrap();
+        assert_eq!(formatted, "---\r\ncodename: x\r\nversion: v1.0.0\r\nowner: ops\r\n---\r\nbody\r\n");
+    }
+}
@@ -0,0 +1,769 @@
+// File: taskline-core/src/lib.rs
+// --- Stable core shared by the taskline CLI, its components and third-party tools
+// --- Header parsing, versions, version requirements, config and errors
+
+//! Core types of the Taskline scripting framework.
+//!
+//! This crate owns everything needed to understand a `.tskln` file without the CLI:
+//! [`TasklineMetadata`] parses `@Taskline` headers, [`Version`] and [`VersionReq`]
+//! handle versions and constraints, [`config`] reads `.taskline.toml`, and
+//! [`TasklineError`] is the shared error type. Items exported here follow semver;
+//! new header fields and error variants are additive (both types are `#[non_exhaustive]`).
+
+#![deny(missing_docs)]
+
+pub mod author;
+pub mod ci;
+pub mod codename;
+pub mod config;
+#[cfg(feature = "fs")]
+pub mod crash;
+#[cfg(feature = "fs")]
+pub mod executable;
+#[cfg(feature = "yaml")]
+pub mod frontmatter;
+pub mod license;
+pub mod line_ending;
+pub mod naming;
+pub mod requirements;
+pub mod scan;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod version_req;
+pub mod versioned;
+pub mod workspace;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use author::Author;
+pub use codename::Codename;
+pub use line_ending::LineEnding;
+pub use version_req::VersionReq;
+
+/// Core error type for Taskline operations
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum TasklineError {
+    /// Malformed version or version requirement
+    VersionError(String),
+    /// Filesystem failure
+    FileError(String),
+    /// Malformed script or data file
+    ParseError(String),
+    /// Invalid `.taskline.toml`
+    ConfigError(String),
+    /// Script registry failure
+    RegistryError(String),
+    /// Script encryption or decryption failure
+    EncryptionError(String),
+}
+
+impl std::fmt::Display for TasklineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TasklineError::VersionError(msg) => write!(f, "Version Error: {}", msg),
+            TasklineError::FileError(msg) => write!(f, "File Error: {}", msg),
+            TasklineError::ParseError(msg) => write!(f, "Parse Error: {}", msg),
+            TasklineError::ConfigError(msg) => write!(f, "Config Error: {}", msg),
+            TasklineError::RegistryError(msg) => write!(f, "Registry Error: {}", msg),
+            TasklineError::EncryptionError(msg) => write!(f, "Encryption Error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TasklineError {}
+
+/// Ultra-fast version representation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Version {
+    /// Incompatible changes
+    pub major: u32,
+    /// Backwards-compatible additions
+    pub minor: u32,
+    /// Backwards-compatible fixes
+    pub patch: u32,
+}
+
+impl Version {
+    /// Create a new version
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+    
+    /// Parse `vX.Y.Z` or `X.Y.Z`. The `v` is optional on input; [`Display`](std::fmt::Display)
+    /// always writes the canonical `vX.Y.Z` form.
+    pub fn parse(version_str: &str) -> Result<Self, TasklineError> {
+        let digits = version_str.strip_prefix('v').unwrap_or(version_str);
+        let parts: Vec<&str> = digits.split('.').collect();
+        if parts.len() != 3 {
+            return Err(TasklineError::VersionError("Version must have format v1.2.3".to_string()));
+        }
+        
+        let major = parse_component(parts[0], "major")?;
+        let minor = parse_component(parts[1], "minor")?;
+        let patch = parse_component(parts[2], "patch")?;
+        
+        Ok(Version::new(major, minor, patch))
+    }
+    
+    /// Bump patch version; fails when the patch component is already at its maximum
+    pub fn bump_patch(self) -> Result<Self, TasklineError> {
+        Ok(Self::new(self.major, self.minor, increment(self.patch, "patch")?))
+    }
+    
+    /// Bump minor version (resets patch); fails when the minor component is already at its maximum
+    pub fn bump_minor(self) -> Result<Self, TasklineError> {
+        Ok(Self::new(self.major, increment(self.minor, "minor")?, 0))
+    }
+    
+    /// Bump major version (resets minor and patch); fails when the major component is already at its maximum
+    pub fn bump_major(self) -> Result<Self, TasklineError> {
+        Ok(Self::new(increment(self.major, "major")?, 0, 0))
+    }
+}
+
+/// `component + 1`, or an error instead of wrapping around
+fn increment(component: u32, name: &str) -> Result<u32, TasklineError> {
+    component.checked_add(1)
+        .ok_or_else(|| TasklineError::VersionError(format!("The {} version {} cannot be bumped any further", name, component)))
+}
+
+/// Digits only: `u32::from_str` alone would also accept a leading `+`
+fn parse_component(part: &str, name: &str) -> Result<u32, TasklineError> {
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(TasklineError::VersionError(format!("Invalid {} version", name)));
+    }
+    part.parse().map_err(|_| TasklineError::VersionError(format!("{} version is too large", name)))
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Serialized as the canonical `vX.Y.Z` string
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        Version::parse(&version).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Taskline file metadata. Serializes to the shape of the metadata JSON Schema
+/// (`taskline schema`): absent fields are omitted and authors are `{name, email}` objects.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TasklineMetadata {
+    /// `@Taskline codename`
+    pub codename: String,
+    /// `@Taskline version`, when present and valid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<Version>,
+    /// `@Taskline author` lines (`Name <email>`), in order; a script may credit several
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<Author>,
+    /// `@Taskline created` date (`YYYY-MM-DD`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// `@Taskline expires` date (`YYYY-MM-DD`), the last day the script may be used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    /// `@Taskline license`, an SPDX expression such as `MIT OR Apache-2.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// `@Taskline deprecated ["message"]`: the script is retired; the message usually
+    /// names its replacement and may be empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// Directories from `@Taskline path`, relative to the script's directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<String>,
+    /// Raw `@Taskline requires` values, see [`TasklineMetadata::requirements`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Raw `@Taskline requires-taskline` value, a requirement on the Taskline CLI such
+    /// as `>=0.3`; see [`TasklineMetadata::check_taskline_version`]
+    #[serde(default, rename = "requires-taskline", skip_serializing_if = "Option::is_none")]
+    pub requires_taskline: Option<String>,
+}
+
+impl TasklineMetadata {
+    /// Parse metadata from file content. With the `yaml` feature, a leading
+    /// `--- ... ---` frontmatter block is read instead of `@Taskline` lines.
+    pub fn parse(content: &str) -> Self {
+        #[cfg(feature = "yaml")]
+        if let Some((yaml, _)) = frontmatter::split_frontmatter(content) {
+            return Self::from_frontmatter(yaml).unwrap_or_else(|_| Self::empty());
+        }
+        
+        let mut codename = String::new();
+        let mut version = None;
+        let mut authors = Vec::new();
+        let mut created = None;
+        let mut expires = None;
+        let mut license = None;
+        let mut deprecated = None;
+        let mut path = Vec::new();
+        let mut requires = Vec::new();
+        let mut requires_taskline = None;
+        
+        for line in content.lines() {
+            if let Some(rest) = line.strip_prefix("@Taskline codename ") {
+                codename = rest.to_string();
+            } else if let Some(rest) = line.strip_prefix("@Taskline version ") {
+                if let Ok(v) = Version::parse(rest) {
+                    version = Some(v);
+                }
+            } else if let Some(rest) = line.strip_prefix("@Taskline author ") {
+                if let Ok(author) = Author::parse(rest) {
+                    authors.push(author);
+                }
+            } else if let Some(rest) = line.strip_prefix("@Taskline created ") {
+                created = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("@Taskline expires ") {
+                expires = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("@Taskline license ") {
+                license = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("@Taskline deprecated") {
+                if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                    deprecated = Some(unquote(rest.trim()).to_string());
+                }
+            } else if let Some(rest) = line.strip_prefix("@Taskline path ") {
+                // Split like PATH itself, so `C:\tools` stays one entry on Windows
+                path.extend(std::env::split_paths(rest.trim())
+                    .map(|entry| entry.to_string_lossy().trim().to_string())
+                    .filter(|entry| !entry.is_empty()));
+            } else if let Some(rest) = line.strip_prefix("@Taskline requires ") {
+                requires.push(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("@Taskline requires-taskline ") {
+                requires_taskline = Some(rest.trim().to_string());
+            }
+        }
+        
+        Self { codename, version, authors, created, expires, license, deprecated, path, requires, requires_taskline }
+    }
+    
+    /// Parse metadata, rejecting headers [`TasklineMetadata::parse`] would silently skip:
+    /// a missing codename, an invalid version or author, an `expires` value that is not a
+    /// date, a license that is not a valid SPDX expression or an unparsable `requires` or
+    /// `requires-taskline` line
+    pub fn validate(content: &str) -> Result<Self, TasklineError> {
+        #[cfg(feature = "yaml")]
+        if let Some((yaml, _)) = frontmatter::split_frontmatter(content) {
+            let metadata = Self::from_frontmatter(yaml)?;
+            if metadata.codename.trim().is_empty() {
+                return Err(TasklineError::ParseError("Missing codename in frontmatter".to_string()));
+            }
+            metadata.check_fields()?;
+            return Ok(metadata);
+        }
+        let metadata = Self::parse(content);
+        if metadata.codename.trim().is_empty() {
+            return Err(TasklineError::ParseError("Missing @Taskline codename".to_string()));
+        }
+        for line in content.lines() {
+            if let Some(rest) = line.strip_prefix("@Taskline version ") {
+                Version::parse(rest)?;
+            } else if let Some(rest) = line.strip_prefix("@Taskline author ") {
+                Author::parse(rest)?;
+            }
+        }
+        metadata.check_fields()?;
+        Ok(metadata)
+    }
+    
+    /// Checks shared by both header formats
+    fn check_fields(&self) -> Result<(), TasklineError> {
+        if let Some(expires) = self.expires.as_deref().filter(|date| !is_date(date)) {
+            return Err(TasklineError::ParseError(format!("Invalid @Taskline expires '{}': expected YYYY-MM-DD", expires)));
+        }
+        if let Some(expression) = &self.license {
+            license::validate_license(expression)?;
+        }
+        self.taskline_requirement()?;
+        self.requirements().map(drop)
+    }
+    
+    #[cfg(feature = "yaml")]
+    fn empty() -> Self {
+        Self { codename: String::new(), version: None, authors: Vec::new(), created: None, expires: None, license: None, deprecated: None, path: Vec::new(), requires: Vec::new(), requires_taskline: None }
+    }
+    
+    /// Whether the script is past its `@Taskline expires` date, given today as `YYYY-MM-DD`.
+    /// The expiry date itself is still valid.
+    pub fn is_expired(&self, today: &str) -> bool {
+        self.expires.as_deref().is_some_and(|expires| is_date(expires) && today > expires)
+    }
+    
+    /// Generate header content
+    pub fn to_header(&self) -> String {
+        let mut header = format!("@Taskline codename {}\n", self.codename);
+        if let Some(v) = &self.version {
+            header.push_str(&format!("@Taskline version {}\n", v));
+        }
+        for author in &self.authors {
+            header.push_str(&format!("@Taskline author {}\n", author));
+        }
+        if let Some(created) = &self.created {
+            header.push_str(&format!("@Taskline created {}\n", created));
+        }
+        if let Some(expires) = &self.expires {
+            header.push_str(&format!("@Taskline expires {}\n", expires));
+        }
+        if let Some(license) = &self.license {
+            header.push_str(&format!("@Taskline license {}\n", license));
+        }
+        match self.deprecated.as_deref() {
+            Some("") => header.push_str("@Taskline deprecated\n"),
+            Some(message) => header.push_str(&format!("@Taskline deprecated \"{}\"\n", message)),
+            None => {}
+        }
+        if !self.path.is_empty() {
+            header.push_str(&format!("@Taskline path {}\n", self.path.join(constants::PATH_SEPARATOR)));
+        }
+        for requires in &self.requires {
+            header.push_str(&format!("@Taskline requires {}\n", requires));
+        }
+        if let Some(req) = &self.requires_taskline {
+            header.push_str(&format!("@Taskline requires-taskline {}\n", req));
+        }
+        header.push('\n');
+        header
+    }
+    
+    /// Parse all `@Taskline requires` lines into tool requirements
+    pub fn requirements(&self) -> Result<Vec<requirements::ToolRequirement>, TasklineError> {
+        let mut all = Vec::new();
+        for value in &self.requires {
+            all.extend(requirements::ToolRequirement::parse_list(value)?);
+        }
+        Ok(all)
+    }
+    
+    /// Parse `@Taskline requires-taskline`, if present
+    pub fn taskline_requirement(&self) -> Result<Option<VersionReq>, TasklineError> {
+        self.requires_taskline.as_deref().map(VersionReq::parse).transpose()
+    }
+    
+    /// Check `@Taskline requires-taskline` against the running Taskline version,
+    /// failing with an upgrade hint when it is too old
+    pub fn check_taskline_version(&self, current: &Version) -> Result<(), TasklineError> {
+        match self.taskline_requirement()? {
+            Some(req) if !req.matches(current) => Err(TasklineError::VersionError(format!(
+                "This script requires Taskline {}, but this is Taskline {}; upgrade with `cargo install taskline`",
+                req, current
+            ))),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Build a PATH value with the script's `@Taskline path` entries prepended.
+    /// Relative entries are resolved against `script_dir`.
+    pub fn augmented_path(&self, script_dir: &std::path::Path, current: Option<&std::ffi::OsStr>) -> Result<std::ffi::OsString, TasklineError> {
+        let local = self.path.iter().map(|entry| script_dir.join(entry));
+        let inherited = current.map(|p| std::env::split_paths(p).collect::<Vec<_>>()).unwrap_or_default();
+        std::env::join_paths(local.chain(inherited))
+            .map_err(|e| TasklineError::ParseError(format!("Invalid @Taskline path entry: {}", e)))
+    }
+}
+
+/// Whether a header line is a comment (`//` or `#`); comments touching the directives
+/// belong to the header
+pub fn is_header_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("//") || line.starts_with('#')
+}
+
+/// Split content into its leading `@Taskline` header block (directives, the comment
+/// lines among them and the blank lines after it) and the body
+pub fn split_header(content: &str) -> (&str, &str) {
+    // The scan ends on a line boundary, so this is always a char boundary
+    content.split_at(scan::header_len(content.as_bytes()))
+}
+
+/// Validate the header of a script given as raw, untrusted bytes. Only the header has to
+/// be UTF-8. Never panics, which makes it a fuzzing entry point.
+pub fn parse_header_bytes(bytes: &[u8]) -> Result<TasklineMetadata, TasklineError> {
+    let header = scan::read_header(bytes).map_err(|e| TasklineError::ParseError(e.to_string()))?;
+    let header = std::str::from_utf8(&header)
+        .map_err(|_| TasklineError::ParseError("Header is not valid UTF-8".to_string()))?;
+    TasklineMetadata::validate(header)
+}
+
+/// [`Version::parse`] on raw bytes; never panics
+pub fn parse_version_bytes(bytes: &[u8]) -> Result<Version, TasklineError> {
+    let version = std::str::from_utf8(bytes)
+        .map_err(|_| TasklineError::VersionError("Version is not valid UTF-8".to_string()))?;
+    Version::parse(version)
+}
+
+/// [`VersionReq::parse`] on raw bytes; never panics
+pub fn parse_requirement_bytes(bytes: &[u8]) -> Result<VersionReq, TasklineError> {
+    let req = std::str::from_utf8(bytes)
+        .map_err(|_| TasklineError::VersionError("Version requirement is not valid UTF-8".to_string()))?;
+    VersionReq::parse(req)
+}
+
+/// Re-render the header canonically, keeping the body untouched. Known directives are
+/// normalized and ordered among the lines they occupy; comments and unknown `@Taskline`
+/// directives stay exactly where they are. The dominant line ending and the absence of
+/// a final newline are preserved.
+pub fn format(content: &str) -> Result<String, TasklineError> {
+    #[cfg(feature = "yaml")]
+    if let Some((yaml, body)) = frontmatter::split_frontmatter(content) {
+        return frontmatter::format_frontmatter(content, yaml, body);
+    }
+    let metadata = TasklineMetadata::validate(content)?;
+    let (header, body) = split_header(content);
+
+    let canonical = metadata.to_header();
+    let mut canonical = canonical.lines().filter(|line| !line.is_empty());
+    let mut lines = Vec::new();
+    let mut after_known = 0;
+    for line in header.lines().map(str::trim_end).filter(|line| !line.is_empty()) {
+        match line.strip_prefix("@Taskline ") {
+            Some(directive) if is_known_directive(directive) => {
+                // Duplicates folded by the parser (e.g. several `path` lines) leave no line
+                if let Some(known) = canonical.next() {
+                    lines.push(known);
+                    after_known = lines.len();
+                }
+            }
+            _ => lines.push(line),
+        }
+    }
+    let remaining: Vec<&str> = canonical.collect();
+    lines.splice(after_known..after_known, remaining);
+
+    let mut formatted = lines.join("\n");
+    formatted.push_str("\n\n");
+    if body.is_empty() && !content.ends_with('\n') {
+        formatted.truncate(formatted.trim_end_matches('\n').len());
+    }
+    let mut formatted = LineEnding::detect(content).apply(&formatted);
+    formatted.push_str(body);
+    Ok(formatted)
+}
+
+/// `YYYY-MM-DD` with a plausible month and day; such dates compare correctly as strings
+fn is_date(s: &str) -> bool {
+    let b = s.as_bytes();
+    let number = |range: std::ops::Range<usize>| s[range].parse::<u32>().unwrap_or(0);
+    b.len() == 10
+        && b[4] == b'-'
+        && b[7] == b'-'
+        && b.iter().enumerate().all(|(i, c)| i == 4 || i == 7 || c.is_ascii_digit())
+        && (1..=12).contains(&number(5..7))
+        && (1..=31).contains(&number(8..10))
+}
+
+/// Strip one pair of matching double or single quotes
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote).and_then(|v| v.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Whether a directive (the text after `@Taskline `) is one [`TasklineMetadata`] models
+fn is_known_directive(directive: &str) -> bool {
+    const KNOWN: [&str; 10] = [
+        "codename", "version", "author", "created", "expires", "license", "deprecated", "path", "requires", "requires-taskline",
+    ];
+    let key = directive.split_once(' ').map_or(directive, |(key, _)| key);
+    KNOWN.contains(&key)
+}
+
+/// Common constants
+pub mod constants {
+    /// Extension of plain scripts
+    pub const TASKLINE_EXTENSION: &str = "tskln";
+    /// Extension of encrypted scripts
+    pub const ENCRYPTED_EXTENSION: &str = "tskbe";
+    /// Version given to scripts that do not declare one
+    pub const DEFAULT_VERSION: &str = "v0.0.1";
+    /// Separator between `@Taskline path` entries, the platform's `PATH` separator
+    pub const PATH_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parsing() {
+        assert_eq!(Version::parse("v1.2.3").unwrap(), Version::new(1, 2, 3));
+        assert_eq!(Version::parse("v0.0.1").unwrap(), Version::new(0, 0, 1));
+        assert_eq!(Version::parse("1.2.3").unwrap(), Version::new(1, 2, 3)); // 'v' is optional
+        assert_eq!(Version::parse("1.2.3").unwrap().to_string(), "v1.2.3"); // Canonical output
+        assert!(Version::parse("vv1.2.3").is_err());
+        assert!(Version::parse("v1.2").is_err()); // Missing patch
+        assert!(Version::parse("v+1.2.3").is_err()); // Sign is not a digit
+        assert!(Version::parse("v1..3").is_err()); // Empty component
+        assert!(Version::parse("v4294967296.0.0").is_err()); // Overflows u32
+    }
+
+    #[test]
+    fn test_version_bumping() {
+        let v = Version::new(1, 2, 3);
+        assert_eq!(v.bump_patch().unwrap(), Version::new(1, 2, 4));
+        assert_eq!(v.bump_minor().unwrap(), Version::new(1, 3, 0));
+        assert_eq!(v.bump_major().unwrap(), Version::new(2, 0, 0));
+        
+        let max = Version::new(u32::MAX, u32::MAX, u32::MAX);
+        assert!(max.bump_patch().is_err());
+        assert!(max.bump_minor().is_err());
+        assert!(max.bump_major().is_err());
+        assert_eq!(Version::new(u32::MAX, 1, u32::MAX).bump_minor().unwrap(), Version::new(u32::MAX, 2, 0));
+    }
+
+    #[test]
+    fn test_metadata_parsing() {
+        let content = "@Taskline codename test-script\n@Taskline version v1.2.3\n\n// Content";
+        let meta = TasklineMetadata::parse(content);
+        assert_eq!(meta.codename, "test-script");
+        assert_eq!(meta.version, Some(Version::new(1, 2, 3)));
+        assert!(meta.path.is_empty());
+        assert!(meta.requires.is_empty());
+        assert!(meta.authors.is_empty());
+    }
+
+    #[test]
+    fn test_metadata_validate() {
+        assert!(TasklineMetadata::validate("@Taskline codename ok\n@Taskline version v1.0.0\n").is_ok());
+        assert!(TasklineMetadata::validate("@Taskline version v1.0.0\n").is_err());
+        assert!(TasklineMetadata::validate("@Taskline codename ok\n@Taskline version 1.0\n").is_err());
+        assert!(TasklineMetadata::validate("@Taskline codename ok\n@Taskline requires node >=\n").is_err());
+    }
+
+    #[test]
+    fn test_split_header_and_format() {
+        let content = "@Taskline version v1.0.0\r\n@Taskline codename fmt\r\n@Taskline custom x\r\n\r\n\r\nbody\n@Taskline codename not-header\n";
+        let (header, body) = split_header(content);
+        assert_eq!(header.lines().count(), 5);
+        assert_eq!(body, "body\n@Taskline codename not-header\n");
+        assert_eq!(split_header("no header\n"), ("", "no header\n"));
+
+        let formatted = format("@Taskline version v1.0.0\n@Taskline codename fmt\n@Taskline custom x\nbody\n").unwrap();
+        assert_eq!(formatted, "@Taskline codename fmt\n@Taskline version v1.0.0\n@Taskline custom x\n\nbody\n");
+        assert!(format("@Taskline codename fmt\n@Taskline version 1.0\n").is_err());
+    }
+
+    #[test]
+    fn test_format_keeps_comments_and_unknown_directives_in_place() {
+        let content = "# deploy pipeline\n@Taskline version 1.0.0\n// owned by ops\n@Taskline team   ops  \n@Taskline codename fmt\n// trailing note\n\n\nbody\n";
+        let formatted = format(content).unwrap();
+        assert_eq!(
+            formatted,
+            "# deploy pipeline\n@Taskline codename fmt\n// owned by ops\n@Taskline team   ops\n@Taskline version v1.0.0\n// trailing note\n\nbody\n"
+        );
+        assert_eq!(format(&formatted).unwrap(), formatted);
+
+        // A comment after the blank separator is body
+        let (header, body) = split_header("@Taskline codename x\n\n// body comment\n");
+        assert_eq!((header, body), ("@Taskline codename x\n\n", "// body comment\n"));
+        assert_eq!(split_header("// no directives\nbody\n"), ("", "// no directives\nbody\n"));
+    }
+
+    #[test]
+    fn test_format_preserves_line_endings() {
+        let formatted = format("@Taskline version v1.0.0\r\n@Taskline codename fmt\r\n\r\nbody\r\n").unwrap();
+        assert_eq!(formatted, "@Taskline codename fmt\r\n@Taskline version v1.0.0\r\n\r\nbody\r\n");
+        assert_eq!(format("@Taskline codename fmt").unwrap(), "@Taskline codename fmt");
+        assert_eq!(format("@Taskline codename fmt\r\n").unwrap(), "@Taskline codename fmt\r\n\r\n");
+    }
+
+    #[test]
+    fn test_metadata_provenance_directives() {
+        let content = "@Taskline codename test-script\n@Taskline version v1.0.0\n@Taskline author Jane Doe <jane@example.com>\n@Taskline created 2026-10-16\n\n";
+        let meta = TasklineMetadata::parse(content);
+        assert_eq!(meta.authors, [Author::new("Jane Doe", Some("jane@example.com".to_string()))]);
+        assert_eq!(meta.created.as_deref(), Some("2026-10-16"));
+        assert_eq!(meta.to_header(), content);
+    }
+
+    #[test]
+    fn test_metadata_license() {
+        let content = "@Taskline codename x\n@Taskline created 2026-10-16\n@Taskline license MIT OR Apache-2.0\n\n";
+        let meta = TasklineMetadata::validate(content).unwrap();
+        assert_eq!(meta.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(meta.to_header(), content);
+        assert!(TasklineMetadata::validate("@Taskline codename x\n@Taskline license Proprietary-ish\n").is_err());
+    }
+
+    #[test]
+    fn test_metadata_expires() {
+        let content = "@Taskline codename migrate\n@Taskline expires 2025-12-31\n\n";
+        let meta = TasklineMetadata::validate(content).unwrap();
+        assert_eq!(meta.to_header(), content);
+        assert!(!meta.is_expired("2025-12-30"));
+        assert!(!meta.is_expired("2025-12-31"));
+        assert!(meta.is_expired("2026-01-01"));
+        assert!(!TasklineMetadata::parse("@Taskline codename x\n").is_expired("2026-01-01"));
+
+        assert!(TasklineMetadata::validate("@Taskline codename x\n@Taskline expires next week\n").is_err());
+        assert!(TasklineMetadata::validate("@Taskline codename x\n@Taskline expires 2025-13-01\n").is_err());
+    }
+
+    #[test]
+    fn test_metadata_serde() {
+        let content = "@Taskline codename deploy\n@Taskline version 1.2.0\n@Taskline author Ada <ada@example.com>\n@Taskline author Ops\n@Taskline requires-taskline >=0.3\n";
+        let metadata = TasklineMetadata::validate(content).unwrap();
+        let toml = toml::to_string(&metadata).unwrap();
+        assert!(toml.contains("version = \"v1.2.0\"") && toml.contains("requires-taskline = \">=0.3\""));
+        assert!(!toml.contains("license"));
+        let parsed: TasklineMetadata = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.to_header(), metadata.to_header());
+        assert_eq!(parsed.authors[1], Author::new("Ops", None));
+
+        assert!(toml::from_str::<TasklineMetadata>("codename = \"x\"\nversion = \"1.0\"\n").is_err());
+        assert!(toml::from_str::<TasklineMetadata>("codename = \"x\"\nunknown = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_metadata_deprecated() {
+        let content = "@Taskline codename old\n@Taskline deprecated \"use deploy-v2.tskln\"\n\n";
+        let meta = TasklineMetadata::parse(content);
+        assert_eq!(meta.deprecated.as_deref(), Some("use deploy-v2.tskln"));
+        assert_eq!(meta.to_header(), content);
+
+        let bare = TasklineMetadata::parse("@Taskline codename old\n@Taskline deprecated\n");
+        assert_eq!(bare.deprecated.as_deref(), Some(""));
+        assert_eq!(bare.to_header(), "@Taskline codename old\n@Taskline deprecated\n\n");
+        assert_eq!(TasklineMetadata::parse("@Taskline deprecatedness x\n").deprecated, None);
+    }
+
+    #[test]
+    fn test_metadata_multiple_authors() {
+        let content = "@Taskline codename team-script
+@Taskline author Jane Doe <jane@example.com>
+@Taskline author Platform Team <platform@example.com>
+@Taskline author Sam
+
+";
+        let meta = TasklineMetadata::validate(content).unwrap();
+        let names: Vec<&str> = meta.authors.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["Jane Doe", "Platform Team", "Sam"]);
+        assert_eq!(meta.authors[1].email.as_deref(), Some("platform@example.com"));
+        assert_eq!(meta.to_header(), content);
+
+        assert!(TasklineMetadata::validate("@Taskline codename x\n@Taskline author Jane <jane\n").is_err());
+    }
+
+    #[test]
+    fn test_metadata_requires_taskline() {
+        let content = "@Taskline codename x\n@Taskline requires docker\n@Taskline requires-taskline >=0.3\n\n";
+        let meta = TasklineMetadata::validate(content).unwrap();
+        assert_eq!(meta.requires, ["docker"]);
+        assert_eq!(meta.to_header(), content);
+        assert!(meta.check_taskline_version(&Version::new(0, 3, 0)).is_ok());
+        assert!(meta.check_taskline_version(&Version::new(1, 0, 0)).is_ok());
+        let err = meta.check_taskline_version(&Version::new(0, 2, 9)).unwrap_err().to_string();
+        assert!(err.contains("requires Taskline >=0.3") && err.contains("upgrade"), "{}", err);
+
+        assert!(TasklineMetadata::parse("@Taskline codename x\n").check_taskline_version(&Version::new(0, 1, 0)).is_ok());
+        assert!(TasklineMetadata::validate("@Taskline codename x\n@Taskline requires-taskline >>0.3\n").is_err());
+    }
+
+    #[test]
+    fn test_metadata_requires_directive() {
+        let content = "@Taskline codename test-script\n@Taskline requires node >= 18, docker\n@Taskline requires git\n";
+        let meta = TasklineMetadata::parse(content);
+        let tools: Vec<_> = meta.requirements().unwrap().into_iter().map(|r| r.tool).collect();
+        assert_eq!(tools, vec!["node", "docker", "git"]);
+        assert!(meta.to_header().contains("@Taskline requires node >= 18, docker\n@Taskline requires git\n"));
+
+        let broken = TasklineMetadata::parse("@Taskline requires node >=\n");
+        assert!(broken.requirements().is_err());
+    }
+
+    #[test]
+    fn test_metadata_path_directive() {
+        let content = format!("@Taskline codename test-script\n@Taskline path ./node_modules/.bin{}./bin\n@Taskline path tools\n", constants::PATH_SEPARATOR);
+        let meta = TasklineMetadata::parse(&content);
+        assert_eq!(meta.path, vec!["./node_modules/.bin", "./bin", "tools"]);
+        assert_eq!(meta.to_header(), format!("@Taskline codename test-script\n@Taskline path ./node_modules/.bin{0}./bin{0}tools\n\n", constants::PATH_SEPARATOR));
+        // Only the platform's PATH separator splits entries
+        #[cfg(windows)]
+        assert_eq!(TasklineMetadata::parse("@Taskline path C:\\tools;D:\\bin\n").path, vec!["C:\\tools", "D:\\bin"]);
+        #[cfg(unix)]
+        assert_eq!(TasklineMetadata::parse("@Taskline path bin;tools\n").path, vec!["bin;tools"]);
+
+        let dir = std::path::Path::new("/work/project");
+        let inherited = std::env::join_paths(["/usr/bin"]).unwrap();
+        let path = meta.augmented_path(dir, Some(&inherited)).unwrap();
+        let entries: Vec<_> = std::env::split_paths(&path).collect();
+        assert_eq!(entries[0], dir.join("./node_modules/.bin"));
+        assert_eq!(entries[2], dir.join("tools"));
+        assert_eq!(entries[3], std::path::PathBuf::from("/usr/bin"));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let script = b"@Taskline codename deploy\n@Taskline version v1.0.0\n\n\xff\xfe body";
+        assert_eq!(parse_header_bytes(script).unwrap().codename, "deploy");
+        assert!(parse_header_bytes(b"@Taskline codename \xff\n").is_err());
+        assert_eq!(parse_version_bytes(b"v2.0.1").unwrap(), Version::new(2, 0, 1));
+        assert!(parse_version_bytes(b"v1.\xc3.0").is_err());
+        assert!(parse_requirement_bytes(b">=1.2, <2").unwrap().matches(&Version::new(1, 9, 0)));
+    }
+
+    /// Deterministic fuzzing: splice header fragments and odd bytes; nothing may panic
+    #[test]
+    fn test_parse_bytes_never_panics() {
+        const FRAGMENTS: [&[u8]; 16] = [
+            b"@Taskline ", b"codename ", b"version ", b"author ", b"requires ", b"requires-taskline ", b"expires ",
+            b"license ", b"deprecated", b"\n", b"\r\n", b"---\n", b"<", b">=", b"1.2.", "\u{e9}\u{1F600}".as_bytes(),
+        ];
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..5_000 {
+            let mut input = Vec::new();
+            for _ in 0..next() % 24 {
+                match next() % 4 {
+                    0 => input.push(next() as u8),
+                    _ => input.extend_from_slice(FRAGMENTS[next() as usize % FRAGMENTS.len()]),
+                }
+            }
+            let _ = parse_header_bytes(&input);
+            let _ = parse_version_bytes(&input);
+            let _ = parse_requirement_bytes(&input);
+            if let Ok(content) = std::str::from_utf8(&input) {
+                let _ = format(content);
+            }
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_metadata_renders() {
+        use arbitrary::{Arbitrary, Unstructured};
+        let bytes: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8).collect();
+        let mut u = Unstructured::new(&bytes);
+        while let Ok(metadata) = TasklineMetadata::arbitrary(&mut u) {
+            let _ = parse_header_bytes(metadata.to_header().as_bytes());
+            if u.is_empty() {
+                break;
+            }
+        }
+        let codename = Codename::arbitrary(&mut Unstructured::new(b"\x05../x")).unwrap();
+        assert!(Codename::parse(codename.as_str()).is_ok());
+    }
+}
\ No newline at end of file
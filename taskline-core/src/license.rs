@@ -0,0 +1,33 @@
+// File: taskline-core/src/license.rs
+// --- `@Taskline license <SPDX expression>` validation
+// --- Strict SPDX syntax and identifiers; `LicenseRef-*` covers in-house licenses
+
+//! SPDX license expressions for `@Taskline license`.
+
+use crate::TasklineError;
+
+/// Check that `expression` is a valid SPDX license expression such as
+/// `MIT OR Apache-2.0` or `GPL-2.0-only WITH Classpath-exception-2.0`
+pub fn validate_license(expression: &str) -> Result<(), TasklineError> {
+    spdx::Expression::parse(expression.trim())
+        .map(drop)
+        .map_err(|e| TasklineError::ParseError(format!("Invalid SPDX license expression '{}': {}", expression, e.reason)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_license() {
+        assert!(validate_license("MIT").is_ok());
+        assert!(validate_license("MIT OR Apache-2.0").is_ok());
+        assert!(validate_license("(MIT AND BSD-3-Clause) OR GPL-2.0-only WITH Classpath-exception-2.0").is_ok());
+        assert!(validate_license("LicenseRef-Acme-Internal").is_ok());
+
+        assert!(validate_license("").is_err());
+        assert!(validate_license("Not-A-License").is_err());
+        assert!(validate_license("MIT OR").is_err());
+        assert!(validate_license("mit or apache").is_err());
+    }
+}
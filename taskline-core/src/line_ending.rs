@@ -0,0 +1,63 @@
+// File: taskline-core/src/line_ending.rs
+// --- Line-ending detection so rewriting tools keep a file's CRLF/LF style
+// --- The dominant ending wins; ties and files without newlines default to LF
+
+//! Line endings of script content.
+
+/// Line ending used by a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// Detect the dominant line ending of `content`
+    pub fn detect(content: impl AsRef<[u8]>) -> Self {
+        let content = content.as_ref();
+        let mut crlf = 0usize;
+        let mut lf = 0usize;
+        for (i, &byte) in content.iter().enumerate() {
+            if byte == b'\n' {
+                if i > 0 && content[i - 1] == b'\r' {
+                    crlf += 1;
+                } else {
+                    lf += 1;
+                }
+            }
+        }
+        if crlf > lf { LineEnding::CrLf } else { LineEnding::Lf }
+    }
+
+    /// The ending as a string
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// Convert every `\n` in LF-only `text` to this ending
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            LineEnding::Lf => text.to_string(),
+            LineEnding::CrLf => text.replace('\n', "\r\n"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\nb\r\nc\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("no newline"), LineEnding::Lf);
+        assert_eq!(LineEnding::CrLf.apply("a\nb\n"), "a\r\nb\r\n");
+    }
+}
@@ -0,0 +1,167 @@
+// File: taskline-core/src/naming.rs
+// --- Team naming policy from the `[naming]` section of `.taskline.toml`
+// --- Rules apply on top of the built-in Codename rules, never instead of them
+
+//! Configurable naming rules for codenames and task names.
+//!
+//! ```toml
+//! [naming.codename]
+//! case = "kebab"                   # kebab | snake | camel | pascal
+//! prefixes = ["ops-", "ci-"]       # any one of these
+//! pattern = "^[a-z]+-[a-z0-9-]+$"  # regular expression the whole name must match
+//! ```
+
+use regex::Regex;
+use serde::Deserialize;
+use crate::TasklineError;
+
+/// `[naming]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingConfig {
+    /// Rules for script codenames
+    pub codename: NamingRule,
+    /// Rules for task names
+    pub task: NamingRule,
+}
+
+impl NamingConfig {
+    /// Reject rules that can never be applied, such as an invalid regular expression
+    pub fn validate(&self) -> Result<(), TasklineError> {
+        self.codename.validate("naming.codename")?;
+        self.task.validate("naming.task")
+    }
+
+    /// Check a codename against the `[naming.codename]` rules
+    pub fn check_codename(&self, name: &str) -> Result<(), TasklineError> {
+        self.codename.check("Codename", name)
+    }
+
+    /// Check a task name against the `[naming.task]` rules
+    pub fn check_task(&self, name: &str) -> Result<(), TasklineError> {
+        self.task.check("Task name", name)
+    }
+}
+
+/// Constraints on one kind of name; every rule that is set must hold
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingRule {
+    /// Regular expression the name must match (use `^...$` to match the whole name)
+    pub pattern: Option<String>,
+    /// Required case style
+    pub case: Option<CaseStyle>,
+    /// The name must start with one of these, when any are given
+    pub prefixes: Vec<String>,
+}
+
+impl NamingRule {
+    fn validate(&self, section: &str) -> Result<(), TasklineError> {
+        if let Some(pattern) = &self.pattern {
+            Regex::new(pattern)
+                .map_err(|e| TasklineError::ConfigError(format!("Invalid pattern in [{}]: {}", section, e)))?;
+        }
+        Ok(())
+    }
+
+    /// Check `name`; `what` names the kind of name in error messages
+    pub fn check(&self, what: &str, name: &str) -> Result<(), TasklineError> {
+        let violation = |rule: String| TasklineError::ParseError(format!("{} '{}' {}", what, name, rule));
+        if !self.prefixes.is_empty() && !self.prefixes.iter().any(|prefix| name.starts_with(prefix.as_str())) {
+            return Err(violation(format!("must start with one of: {}", self.prefixes.join(", "))));
+        }
+        if let Some(case) = self.case {
+            if !case.matches(name) {
+                return Err(violation(format!("must be {}", case)));
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            let regex = Regex::new(pattern)
+                .map_err(|e| TasklineError::ConfigError(format!("Invalid naming pattern '{}': {}", pattern, e)))?;
+            if !regex.is_match(name) {
+                return Err(violation(format!("must match '{}'", pattern)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Case style of a name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseStyle {
+    /// `deploy-prod`
+    Kebab,
+    /// `deploy_prod`
+    Snake,
+    /// `deployProd`
+    Camel,
+    /// `DeployProd`
+    Pascal,
+}
+
+impl CaseStyle {
+    /// Whether `name` is written in this style
+    pub fn matches(self, name: &str) -> bool {
+        let lower_words = |separator: char| {
+            !name.is_empty()
+                && name.split(separator).all(|word| {
+                    !word.is_empty() && word.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+                })
+        };
+        let first = name.bytes().next();
+        let alphanumeric = name.bytes().all(|b| b.is_ascii_alphanumeric());
+        match self {
+            CaseStyle::Kebab => lower_words('-'),
+            CaseStyle::Snake => lower_words('_'),
+            CaseStyle::Camel => alphanumeric && first.is_some_and(|b| b.is_ascii_lowercase()),
+            CaseStyle::Pascal => alphanumeric && first.is_some_and(|b| b.is_ascii_uppercase()),
+        }
+    }
+}
+
+impl std::fmt::Display for CaseStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            CaseStyle::Kebab => "kebab-case",
+            CaseStyle::Snake => "snake_case",
+            CaseStyle::Camel => "camelCase",
+            CaseStyle::Pascal => "PascalCase",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TasklineConfig;
+
+    #[test]
+    fn test_case_styles() {
+        assert!(CaseStyle::Kebab.matches("deploy-prod-2"));
+        assert!(!CaseStyle::Kebab.matches("deploy--prod"));
+        assert!(!CaseStyle::Kebab.matches("Deploy"));
+        assert!(CaseStyle::Snake.matches("deploy_prod"));
+        assert!(!CaseStyle::Snake.matches("deploy-prod"));
+        assert!(CaseStyle::Camel.matches("deployProd"));
+        assert!(!CaseStyle::Camel.matches("DeployProd"));
+        assert!(CaseStyle::Pascal.matches("DeployProd"));
+        assert!(!CaseStyle::Pascal.matches("deploy_prod"));
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        let config = TasklineConfig::parse(
+            "[naming.codename]\ncase = \"kebab\"\nprefixes = [\"ops-\", \"ci-\"]\npattern = \"^[a-z]+-[a-z]+$\"\n",
+        ).unwrap();
+        let naming = &config.naming;
+        assert!(naming.check_codename("ops-deploy").is_ok());
+        assert!(naming.check_codename("deploy").unwrap_err().to_string().contains("ops-, ci-"));
+        assert!(naming.check_codename("ops-Deploy").unwrap_err().to_string().contains("kebab-case"));
+        assert!(naming.check_codename("ops-deploy-2").unwrap_err().to_string().contains("must match"));
+        assert!(naming.check_task("Anything_Goes").is_ok()); // No task rules configured
+
+        assert!(TasklineConfig::parse("[naming.codename]\npattern = \"(\"\n").is_err());
+        assert!(TasklineConfig::parse("[naming.codename]\ncase = \"shouty\"\n").is_err());
+    }
+}
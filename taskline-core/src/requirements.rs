@@ -0,0 +1,177 @@
+// File: taskline-core/src/requirements.rs
+// --- `@Taskline requires node >= 18, docker` tool requirements
+// --- Checked by probing `<tool> --version` on PATH, after the script's `@Taskline path` entries
+
+//! External tools declared with `@Taskline requires`.
+
+#[cfg(feature = "fs")]
+use std::ffi::OsStr;
+#[cfg(feature = "fs")]
+use std::process::{Command, Stdio};
+use crate::{TasklineError, Version, VersionReq};
+
+/// A single external tool a script depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolRequirement {
+    /// Executable name
+    pub tool: String,
+    /// Version constraint, if any
+    pub req: Option<VersionReq>,
+}
+
+/// Outcome of checking one requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequirementStatus {
+    /// Tool found and version (if constrained) satisfied
+    Satisfied(Option<Version>),
+    /// Tool could not be started
+    Missing,
+    /// Tool found but its version does not match
+    Mismatch(Version),
+    /// Tool found but no version could be read from `--version`
+    UnknownVersion,
+}
+
+impl ToolRequirement {
+    /// Parse one comma-separated entry such as `node >= 18` or `docker`
+    pub fn parse(entry: &str) -> Result<Self, TasklineError> {
+        let entry = entry.trim();
+        let split = entry.find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '=' | '^' | '~'))
+            .unwrap_or(entry.len());
+        let (tool, req) = entry.split_at(split);
+        if tool.is_empty() {
+            return Err(TasklineError::ParseError(format!("Requirement '{}' is missing a tool name", entry)));
+        }
+        let req = req.trim();
+        let req = if req.is_empty() { None } else { Some(VersionReq::parse(req)?) };
+        Ok(Self { tool: tool.to_string(), req })
+    }
+
+    /// Parse the value of a `@Taskline requires` line
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, TasklineError> {
+        value.split(',').filter(|e| !e.trim().is_empty()).map(Self::parse).collect()
+    }
+
+    /// Probe the tool on the current `PATH` and compare its reported version against the
+    /// requirement
+    #[cfg(feature = "fs")]
+    pub fn check(&self) -> RequirementStatus {
+        self.check_on(&std::env::var_os("PATH").unwrap_or_default())
+    }
+
+    /// Like [`ToolRequirement::check`], but looking the tool up on `path`, such as
+    /// [`crate::TasklineMetadata::augmented_path`]; the probe runs with that `PATH` too
+    #[cfg(feature = "fs")]
+    pub fn check_on(&self, path: &OsStr) -> RequirementStatus {
+        // Resolved up front so Windows finds `.cmd` and `.bat` shims, not only `.exe`
+        let Some(program) = crate::executable::find_on(&self.tool, path) else { return RequirementStatus::Missing };
+        let output = match Command::new(program).arg("--version").env("PATH", path).stdin(Stdio::null()).output() {
+            Ok(output) => output,
+            Err(_) => return RequirementStatus::Missing,
+        };
+        // Tools that reject `--version` still exist; their output is not a version though
+        if !output.status.success() {
+            return self.evaluate(None);
+        }
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push('\n');
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        self.evaluate(detect_version(&text))
+    }
+
+    /// Compare an already-detected version against the requirement
+    pub fn evaluate(&self, found: Option<Version>) -> RequirementStatus {
+        match (&self.req, found) {
+            (None, found) => RequirementStatus::Satisfied(found),
+            (Some(_), None) => RequirementStatus::UnknownVersion,
+            (Some(req), Some(v)) if req.matches(&v) => RequirementStatus::Satisfied(Some(v)),
+            (Some(_), Some(v)) => RequirementStatus::Mismatch(v),
+        }
+    }
+}
+
+impl std::fmt::Display for ToolRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.req {
+            Some(req) => write!(f, "{} {}", self.tool, req),
+            None => write!(f, "{}", self.tool),
+        }
+    }
+}
+
+/// Find the first `X[.Y[.Z]]` number in `--version` output (`v18.17.0`, `git version 2.39.2`)
+pub fn detect_version(output: &str) -> Option<Version> {
+    output
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|token| token.trim_matches('.'))
+        .find(|token| !token.is_empty())
+        .and_then(|token| {
+            let mut parts = token.split('.').map(|p| p.parse::<u32>());
+            let major = parts.next()?.ok()?;
+            let minor = parts.next().and_then(Result::ok).unwrap_or(0);
+            let patch = parts.next().and_then(Result::ok).unwrap_or(0);
+            Some(Version::new(major, minor, patch))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requirement_list() {
+        let reqs = ToolRequirement::parse_list("node >= 18, docker, python>=3.10 <4").unwrap();
+        assert_eq!(reqs.len(), 3);
+        assert_eq!(reqs[0].tool, "node");
+        assert_eq!(reqs[0].req, Some(VersionReq::parse(">=18").unwrap()));
+        assert_eq!(reqs[1].tool, "docker");
+        assert!(reqs[1].req.is_none());
+        assert_eq!(reqs[2].tool, "python");
+        assert_eq!(reqs[2].to_string(), "python >=3.10, <4");
+
+        assert!(ToolRequirement::parse_list(">= 18").is_err());
+        assert!(ToolRequirement::parse_list("node >=").is_err());
+    }
+
+    #[test]
+    fn test_detect_version() {
+        assert_eq!(detect_version("v18.17.0\n"), Some(Version::new(18, 17, 0)));
+        assert_eq!(detect_version("git version 2.39.2"), Some(Version::new(2, 39, 2)));
+        assert_eq!(detect_version("Docker version 24.0.5, build ced0996"), Some(Version::new(24, 0, 5)));
+        assert_eq!(detect_version("Python 3.11"), Some(Version::new(3, 11, 0)));
+        assert_eq!(detect_version("no digits here"), None);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let node = ToolRequirement::parse("node >= 18").unwrap();
+        assert_eq!(node.evaluate(Some(Version::new(20, 1, 0))), RequirementStatus::Satisfied(Some(Version::new(20, 1, 0))));
+        assert_eq!(node.evaluate(Some(Version::new(16, 0, 0))), RequirementStatus::Mismatch(Version::new(16, 0, 0)));
+        assert_eq!(node.evaluate(None), RequirementStatus::UnknownVersion);
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_check_missing_tool() {
+        let missing = ToolRequirement::parse("definitely-not-a-real-tool-xyz").unwrap();
+        assert_eq!(missing.check(), RequirementStatus::Missing);
+    }
+
+    #[test]
+    #[cfg(all(feature = "fs", unix))]
+    fn test_check_on_declared_path() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("node_modules/.bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let tool = bin.join("taskline-test-tool");
+        std::fs::write(&tool, "#!/bin/sh\necho 'taskline-test-tool 2.4.1'\n").unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let requirement = ToolRequirement::parse("taskline-test-tool >= 2").unwrap();
+        assert_eq!(requirement.check(), RequirementStatus::Missing);
+        let metadata = crate::TasklineMetadata::parse("@Taskline codename deploy\n@Taskline path ./node_modules/.bin\n");
+        let path = metadata.augmented_path(dir.path(), std::env::var_os("PATH").as_deref()).unwrap();
+        assert_eq!(requirement.check_on(&path), RequirementStatus::Satisfied(Some(Version::new(2, 4, 1))));
+    }
+}
@@ -0,0 +1,171 @@
+// File: taskline-core/src/scan.rs
+// --- Byte-level header scanning: find where the header ends without decoding the file
+// --- memchr locates newlines; files are read only until the header is complete
+
+//! Fast header scanning for tools that index many scripts.
+//!
+//! [`header_len`] finds the end of the `@Taskline` header block on raw bytes, and
+//! [`read_header`] reads a script only up to that point, so metadata can be collected
+//! from large files without loading their bodies.
+
+use std::io::Read;
+use memchr::memchr;
+use crate::is_header_comment;
+#[cfg(feature = "fs")]
+use crate::{TasklineError, TasklineMetadata};
+
+const DIRECTIVE: &[u8] = b"@Taskline ";
+const CHUNK: usize = 8 * 1024;
+
+/// Where a scan ended
+struct Scan {
+    /// Header length, as in [`crate::split_header`]
+    end: usize,
+    /// Start of the line that ended the header, or the input length
+    stop: usize,
+}
+
+/// Length in bytes of the leading `@Taskline` header block, the same split as
+/// [`crate::split_header`] but on raw bytes
+pub fn header_len(bytes: &[u8]) -> usize {
+    scan(bytes).end
+}
+
+fn scan(bytes: &[u8]) -> Scan {
+    let mut end = 0; // Header length so far; comments before the first directive are pending
+    let mut scanned = 0;
+    let mut in_header = true;
+    while scanned < bytes.len() {
+        let rest = &bytes[scanned..];
+        let len = memchr(b'\n', rest).map_or(rest.len(), |nl| nl + 1);
+        let line = trim_line_ending(&rest[..len]);
+        if in_header && line.starts_with(DIRECTIVE) {
+            scanned += len;
+            end = scanned;
+        } else if in_header && std::str::from_utf8(line).is_ok_and(is_header_comment) {
+            scanned += len;
+            if end > 0 {
+                end = scanned;
+            }
+        } else if line.is_empty() && end > 0 && scanned == end {
+            in_header = false;
+            scanned += len;
+            end = scanned;
+        } else {
+            break;
+        }
+    }
+    Scan { end, stop: scanned }
+}
+
+fn trim_line_ending(mut line: &[u8]) -> &[u8] {
+    while let [rest @ .., b'\n' | b'\r'] = line {
+        line = rest;
+    }
+    line
+}
+
+/// Read from `reader` only until the header is complete and return its bytes: the
+/// `@Taskline` block of [`header_len`], or a whole `--- ... ---` frontmatter block
+pub fn read_header<R: Read>(mut reader: R) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut eof = false;
+    loop {
+        if !eof {
+            let filled = buf.len();
+            buf.resize(filled + CHUNK, 0);
+            let read = reader.read(&mut buf[filled..])?;
+            buf.truncate(filled + read);
+            eof = read == 0;
+        }
+        if let Some(len) = header_in(&buf, eof) {
+            buf.truncate(len);
+            return Ok(buf);
+        }
+    }
+}
+
+/// Validate the metadata of the script at `path`, reading only its header
+#[cfg(feature = "fs")]
+pub fn read_metadata(path: &std::path::Path) -> Result<TasklineMetadata, TasklineError> {
+    let io = |e: std::io::Error| TasklineError::FileError(format!("{}: {}", path.display(), e));
+    let header = read_header(std::fs::File::open(path).map_err(io)?).map_err(io)?;
+    let header = String::from_utf8(header)
+        .map_err(|_| TasklineError::FileError(format!("{}: header is not valid UTF-8", path.display())))?;
+    TasklineMetadata::validate(&header)
+}
+
+/// Header length once `buf` holds enough to decide it, `None` while more input is needed
+fn header_in(buf: &[u8], eof: bool) -> Option<usize> {
+    let first_line = memchr(b'\n', buf).map(|nl| &buf[..nl + 1]);
+    if first_line.map_or(eof && trim_line_ending(buf) == b"---", |line| trim_line_ending(line) == b"---") {
+        return frontmatter_len(buf).or(if eof { Some(buf.len()) } else { None });
+    }
+    let Scan { end, stop } = scan(buf);
+    // The line that ended the header must be complete, or it might still become a directive
+    (eof || memchr(b'\n', &buf[stop..]).is_some()).then_some(end)
+}
+
+/// Length through the closing `---` line, when it has been read
+fn frontmatter_len(buf: &[u8]) -> Option<usize> {
+    let mut start = memchr(b'\n', buf)? + 1;
+    while start < buf.len() {
+        let len = memchr(b'\n', &buf[start..]).map(|nl| nl + 1)?;
+        if trim_line_ending(&buf[start..start + len]) == b"---" {
+            return Some(start + len);
+        }
+        start += len;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader handing out a few bytes at a time, like a slow pipe
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.1.min(out.len()).min(self.0.len());
+            out[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_header_len() {
+        let cases: [(&str, &str); 6] = [
+            ("@Taskline codename x\n@Taskline version v1.0.0\n\n\nbody\n@Taskline codename y\n", "@Taskline codename x\n@Taskline version v1.0.0\n\n\n"),
+            ("// note\n@Taskline codename x\r\n# trailing\r\n\r\nbody", "// note\n@Taskline codename x\r\n# trailing\r\n\r\n"),
+            ("# just a comment\nbody\n", ""),
+            ("@Taskline codename x", "@Taskline codename x"),
+            ("", ""),
+            ("body only\n", ""),
+        ];
+        for (content, header) in cases {
+            assert_eq!(header_len(content.as_bytes()), header.len(), "{:?}", content);
+        }
+    }
+
+    #[test]
+    fn test_read_header_stops_at_body() {
+        let script = format!("@Taskline codename big\n@Taskline version v1.0.0\n\n{}", "x".repeat(10 * CHUNK));
+        assert_eq!(read_header(script.as_bytes()).unwrap(), b"@Taskline codename big\n@Taskline version v1.0.0\n\n");
+
+        for step in [1, 3, 64] {
+            let content = b"// c\n@Taskline codename x\n@Taskline path bin\n\nbody\n";
+            assert_eq!(read_header(Trickle(content, step)).unwrap(), b"// c\n@Taskline codename x\n@Taskline path bin\n\n");
+            assert_eq!(read_header(Trickle(b"@Taskline codename x", step)).unwrap(), b"@Taskline codename x");
+        }
+    }
+
+    #[test]
+    fn test_read_header_frontmatter() {
+        let script = b"---\ncodename: x\n---\n\nbody\n";
+        assert_eq!(read_header(Trickle(script, 2)).unwrap(), b"---\ncodename: x\n---\n");
+        assert_eq!(read_header(&b"---\ncodename: x\n"[..]).unwrap(), b"---\ncodename: x\n");
+    }
+}
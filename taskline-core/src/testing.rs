@@ -0,0 +1,168 @@
+// File: taskline-core/src/testing.rs
+// --- proptest strategies for property-testing code built on taskline-core
+// --- Valid generators round-trip through to_header/parse; adversarial ones only must not panic
+
+//! [proptest] strategies for the core types (feature `testing`).
+//!
+//! The valid strategies produce values that survive a render/parse round trip, e.g.
+//! `TasklineMetadata::validate(&metadata.to_header())`. The `adversarial_*` strategies
+//! produce input that is usually invalid and only has to be rejected without panicking.
+//!
+//! ```ignore
+//! use proptest::prelude::*;
+//! use taskline_core::testing;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn indexes_any_script(script in testing::script()) {
+//!         my_indexer::index(&script).unwrap();
+//!     }
+//! }
+//! ```
+
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use crate::{Author, Codename, TasklineMetadata, Version};
+
+/// Any version
+pub fn version() -> impl Strategy<Value = Version> {
+    (any::<u32>(), any::<u32>(), any::<u32>()).prop_map(|(major, minor, patch)| Version::new(major, minor, patch))
+}
+
+/// Version-like strings that are mostly invalid: extra or missing components, signs,
+/// leading zeros, overflow, non-ASCII digits and stray whitespace
+pub fn adversarial_version() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[ \t]?[vV+-]{0,2}[0-9]{0,11}(\\.[0-9+*x]{0,11}){0,4}[ \t\n]?",
+        "v?[0-9\u{660}-\u{669}\u{ff10}-\u{ff19}]{1,3}\\.[0-9]{1,3}\\.[0-9]{1,3}",
+        any::<String>(),
+    ]
+}
+
+/// Any valid codename
+pub fn codename() -> impl Strategy<Value = Codename> {
+    "[A-Za-z0-9][A-Za-z0-9_-]{0,63}".prop_filter_map("reserved device name", |name| Codename::parse(&name).ok())
+}
+
+/// An author with an optional email address
+pub fn author() -> impl Strategy<Value = Author> {
+    ("[A-Z][a-z]{0,10}( [A-Z][a-z]{0,10})?", option::of("[a-z]{1,8}@[a-z]{1,8}\\.(com|org|dev)"))
+        .prop_map(|(name, email)| Author::new(name, email))
+}
+
+/// A `YYYY-MM-DD` date
+pub fn date() -> impl Strategy<Value = String> {
+    (1970u32..2200, 1u32..=12, 1u32..=28).prop_map(|(y, m, d)| format!("{:04}-{:02}-{:02}", y, m, d))
+}
+
+/// Metadata with every field valid, so it passes [`TasklineMetadata::validate`] once rendered
+pub fn metadata() -> impl Strategy<Value = TasklineMetadata> {
+    let licenses = prop::sample::select(vec!["MIT", "Apache-2.0", "MIT OR Apache-2.0", "GPL-3.0-only"]);
+    let requires = ("[a-z][a-z0-9-]{0,10}", option::of(prop::sample::select(vec![">=", "^", "~", "<"])), 0u32..30)
+        .prop_map(|(tool, op, major)| match op {
+            Some(op) => format!("{} {}{}", tool, op, major),
+            None => tool,
+        });
+    (
+        codename(),
+        option::of(version()),
+        vec(author(), 0..3),
+        option::of(date()),
+        option::of(date()),
+        option::of(licenses.prop_map(String::from)),
+        option::of("([A-Za-z][A-Za-z ]{0,30}[A-Za-z])?"),
+        vec("[a-z.][a-z0-9_./-]{0,15}", 0..3),
+        vec(requires, 0..3),
+        option::of((0u32..5, 0u32..20).prop_map(|(major, minor)| format!(">={}.{}", major, minor))),
+    )
+        .prop_map(|(codename, version, authors, created, expires, license, deprecated, path, requires, requires_taskline)| {
+            TasklineMetadata {
+                codename: codename.to_string(),
+                version,
+                authors,
+                created,
+                expires,
+                license,
+                deprecated,
+                path,
+                requires,
+                requires_taskline,
+            }
+        })
+}
+
+/// Script body: a non-empty first line and no `@Taskline` lines
+pub fn body() -> impl Strategy<Value = String> {
+    ("[a-z#/][^\r\n]{0,40}", vec("[^\r\n]{0,40}", 0..8)).prop_map(|(first, lines)| {
+        let mut body = first;
+        for line in lines.iter().filter(|line| !line.starts_with("@Taskline")) {
+            body.push('\n');
+            body.push_str(line);
+        }
+        body
+    })
+}
+
+/// A whole valid script: a rendered [`metadata`] header followed by a [`body`]
+pub fn script() -> impl Strategy<Value = String> {
+    (metadata(), body()).prop_map(|(metadata, body)| metadata.to_header() + &body)
+}
+
+/// Header-shaped bytes spliced from directives, separators, CRLF, frontmatter fences,
+/// multi-byte characters and invalid UTF-8
+pub fn adversarial_header() -> impl Strategy<Value = Vec<u8>> {
+    let fragment = prop_oneof![
+        prop::sample::select(vec![
+            "@Taskline ", "codename ", "version ", "author ", "requires ", "requires-taskline ", "expires ",
+            "license ", "deprecated", "path ", "created ", "\n", "\r\n", "\r", "---\n", "# ", "// ", "<", ">=",
+            ", ", ":", ";", "\"", "1.2.", "v0.0.0", "\u{e9}", "\u{1F600}", "\u{FEFF}",
+        ])
+        .prop_map(|s| s.as_bytes().to_vec()),
+        vec(any::<u8>(), 1..4),
+    ];
+    vec(fragment, 0..32).prop_map(|fragments| fragments.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_version_round_trips(v in version()) {
+            prop_assert_eq!(Version::parse(&v.to_string()).unwrap(), v);
+        }
+
+        #[test]
+        fn test_adversarial_versions(s in adversarial_version()) {
+            if let Ok(v) = Version::parse(&s) {
+                prop_assert_eq!(Version::parse(&v.to_string()).unwrap(), v);
+            }
+            let _ = crate::VersionReq::parse(&s);
+        }
+
+        #[test]
+        fn test_metadata_round_trips(metadata in metadata()) {
+            let header = metadata.to_header();
+            let parsed = TasklineMetadata::validate(&header).unwrap();
+            prop_assert_eq!(parsed.to_header(), header);
+        }
+
+        #[test]
+        fn test_script_splits_and_formats(metadata in metadata(), body in body()) {
+            let header = metadata.to_header();
+            let script = header.clone() + &body;
+            prop_assert_eq!(crate::split_header(&script), (header.as_str(), body.as_str()));
+            prop_assert_eq!(crate::format(&script).unwrap(), script);
+        }
+
+        #[test]
+        fn test_adversarial_headers(bytes in adversarial_header()) {
+            let _ = crate::parse_header_bytes(&bytes);
+            if let Ok(content) = std::str::from_utf8(&bytes) {
+                let _ = crate::format(content);
+            }
+        }
+    }
+}
@@ -0,0 +1,392 @@
+// File: taskline-core/src/version_req.rs
+// --- Version constraints (`>= 18`, `^2.1`, `~1.4.2`, `>=1.0 <2`)
+// --- Cargo-style semantics over the core Version type
+
+//! Version requirements such as `^2.1` or `>=1.0, <2`.
+
+use crate::{TasklineError, Version};
+
+/// Comparison operator of a single constraint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Op {
+    /// `=`
+    Exact,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEq,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEq,
+    /// `~`: patch updates only
+    Tilde,
+    /// `^` (or no operator): compatible updates
+    Caret,
+    /// `*`: any version
+    Wildcard,
+}
+
+/// One `op version` pair; missing minor/patch components act as wildcards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Comparator {
+    /// Comparison operator
+    pub op: Op,
+    /// Major component
+    pub major: u32,
+    /// Minor component, `None` when omitted or `*`
+    pub minor: Option<u32>,
+    /// Patch component, `None` when omitted or `*`
+    pub patch: Option<u32>,
+}
+
+/// A set of comparators that must all match
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct VersionReq {
+    /// Comparators that must all match; empty matches everything
+    pub comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// Requirement matching every version
+    pub const STAR: VersionReq = VersionReq { comparators: Vec::new() };
+
+    /// Parse a requirement; comparators are separated by commas or whitespace.
+    /// A bare version (`2.1`) means `^2.1`, like Cargo.
+    pub fn parse(req: &str) -> Result<Self, TasklineError> {
+        let bytes = req.as_bytes();
+        let mut comparators = Vec::new();
+        let mut i = 0;
+
+        loop {
+            while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b',') {
+                i += 1;
+            }
+            if i == bytes.len() {
+                break;
+            }
+
+            let op_start = i;
+            while i < bytes.len() && matches!(bytes[i], b'<' | b'>' | b'=' | b'^' | b'~') {
+                i += 1;
+            }
+            let op = match &req[op_start..i] {
+                "" => None,
+                "=" => Some(Op::Exact),
+                ">" => Some(Op::Greater),
+                ">=" => Some(Op::GreaterEq),
+                "<" => Some(Op::Less),
+                "<=" => Some(Op::LessEq),
+                "~" => Some(Op::Tilde),
+                "^" => Some(Op::Caret),
+                other => return Err(invalid(req, &format!("unknown operator '{}'", other))),
+            };
+
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let version_start = i;
+            while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b',' {
+                i += 1;
+            }
+            let version = &req[version_start..i];
+            if version.is_empty() {
+                return Err(invalid(req, "operator without a version"));
+            }
+
+            comparators.push(Comparator::parse_parts(op, version).map_err(|msg| invalid(req, &msg))?);
+        }
+
+        if comparators.is_empty() {
+            return Err(invalid(req, "empty requirement"));
+        }
+        comparators.retain(|c| c.op != Op::Wildcard);
+        Ok(Self { comparators })
+    }
+
+    /// Whether `version` satisfies every comparator
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+
+    /// Highest version in `versions` satisfying this requirement
+    pub fn highest_match<'a, I: IntoIterator<Item = &'a Version>>(&self, versions: I) -> Option<Version> {
+        versions.into_iter().filter(|v| self.matches(v)).max().copied()
+    }
+
+    /// Whether some version satisfies both requirements (`=3.12` and `>=3.12.1` do,
+    /// `^2` and `>=3` do not)
+    pub fn intersects(&self, other: &VersionReq) -> bool {
+        let mut lower = Version::new(0, 0, 0);
+        let mut upper: Option<Version> = None;
+        for (low, high) in self.comparators.iter().chain(&other.comparators).map(Comparator::bounds) {
+            lower = lower.max(low);
+            upper = match (upper, high) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+        }
+        upper.map_or(true, |upper| lower < upper)
+    }
+}
+
+impl Comparator {
+    fn parse_parts(op: Option<Op>, version: &str) -> Result<Self, String> {
+        let digits = version.strip_prefix('v').unwrap_or(version);
+        if op.is_none() && matches!(digits, "*" | "x" | "X") {
+            return Ok(Self { op: Op::Wildcard, major: 0, minor: None, patch: None });
+        }
+
+        let mut parts = digits.split('.');
+        let major = parse_component(parts.next(), version)?
+            .ok_or_else(|| format!("'{}' needs a major version", version))?;
+        let minor = parse_component(parts.next(), version)?;
+        let patch = match minor {
+            Some(_) => parse_component(parts.next(), version)?,
+            None => None,
+        };
+        if parts.next().is_some() {
+            return Err(format!("'{}' has too many components", version));
+        }
+
+        Ok(Self { op: op.unwrap_or(Op::Caret), major, minor, patch })
+    }
+
+    /// Whether `v` satisfies this comparator
+    pub fn matches(&self, v: &Version) -> bool {
+        match self.op {
+            Op::Exact => self.matches_exact(v),
+            Op::Greater => self.matches_greater(v),
+            Op::GreaterEq => self.matches_exact(v) || self.matches_greater(v),
+            Op::Less => !self.matches_exact(v) && !self.matches_greater(v),
+            Op::LessEq => !self.matches_greater(v),
+            Op::Tilde => self.matches_tilde(v),
+            Op::Caret => self.matches_caret(v),
+            Op::Wildcard => true,
+        }
+    }
+
+    /// The versions this comparator matches as `lower <= v < upper`; `None` is unbounded
+    fn bounds(&self) -> (Version, Option<Version>) {
+        let (major, minor, patch) = (self.major, self.minor.unwrap_or(0), self.patch.unwrap_or(0));
+        let start = Version::new(major, minor, patch);
+        let next_major = major.checked_add(1).map(|m| Version::new(m, 0, 0));
+        let next_minor = |minor: u32| minor.checked_add(1).map_or(next_major, |m| Some(Version::new(major, m, 0)));
+        let next_patch = |patch: u32| patch.checked_add(1).map_or(next_minor(minor), |p| Some(Version::new(major, minor, p)));
+        // The end of the range `=` matches: the next major, minor or patch
+        let end = match (self.minor, self.patch) {
+            (None, _) => next_major,
+            (Some(minor), None) => next_minor(minor),
+            (Some(_), Some(patch)) => next_patch(patch),
+        };
+        let zero = Version::new(0, 0, 0);
+        match self.op {
+            Op::Exact => (start, end),
+            Op::Greater => match end {
+                Some(end) => (end, None),
+                None => (start, Some(start)),
+            },
+            Op::GreaterEq => (start, None),
+            Op::Less => (zero, Some(start)),
+            Op::LessEq => (zero, end),
+            Op::Tilde => match self.minor {
+                Some(minor) => (start, next_minor(minor)),
+                None => (start, next_major),
+            },
+            Op::Caret => match (self.major, self.minor, self.patch) {
+                (0, Some(0), Some(patch)) => (start, next_patch(patch)),
+                (0, Some(minor), _) => (start, next_minor(minor)),
+                _ => (start, next_major),
+            },
+            Op::Wildcard => (zero, None),
+        }
+    }
+
+    fn matches_exact(&self, v: &Version) -> bool {
+        v.major == self.major
+            && self.minor.map_or(true, |m| v.minor == m)
+            && self.patch.map_or(true, |p| v.patch == p)
+    }
+
+    fn matches_greater(&self, v: &Version) -> bool {
+        if v.major != self.major {
+            return v.major > self.major;
+        }
+        let Some(minor) = self.minor else { return false };
+        if v.minor != minor {
+            return v.minor > minor;
+        }
+        let Some(patch) = self.patch else { return false };
+        v.patch > patch
+    }
+
+    fn matches_tilde(&self, v: &Version) -> bool {
+        v.major == self.major
+            && self.minor.map_or(true, |m| v.minor == m)
+            && self.patch.map_or(true, |p| v.patch >= p)
+    }
+
+    fn matches_caret(&self, v: &Version) -> bool {
+        if v.major != self.major {
+            return false;
+        }
+        let Some(minor) = self.minor else { return true };
+        let Some(patch) = self.patch else {
+            return if self.major > 0 { v.minor >= minor } else { v.minor == minor };
+        };
+        if self.major > 0 {
+            v.minor > minor || (v.minor == minor && v.patch >= patch)
+        } else if minor > 0 {
+            v.minor == minor && v.patch >= patch
+        } else {
+            v.minor == minor && v.patch == patch
+        }
+    }
+}
+
+fn parse_component(part: Option<&str>, version: &str) -> Result<Option<u32>, String> {
+    match part {
+        None | Some("*") | Some("x") | Some("X") => Ok(None),
+        Some(p) => p.parse().map(Some).map_err(|_| format!("invalid version '{}'", version)),
+    }
+}
+
+fn invalid(req: &str, msg: &str) -> TasklineError {
+    TasklineError::VersionError(format!("Invalid version requirement '{}': {}", req, msg))
+}
+
+impl std::fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.comparators.is_empty() {
+            return write!(f, "*");
+        }
+        for (i, c) in self.comparators.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Comparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let op = match self.op {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Wildcard => return write!(f, "*"),
+        };
+        write!(f, "{}{}", op, self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{}", minor)?;
+            if let Some(patch) = self.patch {
+                write!(f, ".{}", patch)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(s: &str) -> VersionReq {
+        VersionReq::parse(s).unwrap()
+    }
+
+    fn v(major: u32, minor: u32, patch: u32) -> Version {
+        Version::new(major, minor, patch)
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        assert!(req(">= 18").matches(&v(18, 0, 0)));
+        assert!(req(">=18").matches(&v(20, 1, 0)));
+        assert!(!req(">=18").matches(&v(17, 9, 9)));
+        assert!(req(">1.2").matches(&v(1, 3, 0)));
+        assert!(!req(">1.2").matches(&v(1, 2, 9)));
+        assert!(req("<2").matches(&v(1, 99, 0)));
+        assert!(!req("<2").matches(&v(2, 0, 0)));
+        assert!(req("<=1.2").matches(&v(1, 2, 7)));
+        assert!(req("=1.2").matches(&v(1, 2, 5)));
+        assert!(!req("=1.2.3").matches(&v(1, 2, 4)));
+    }
+
+    #[test]
+    fn test_caret_and_tilde() {
+        assert!(req("^2.1").matches(&v(2, 9, 0)));
+        assert!(!req("^2.1").matches(&v(3, 0, 0)));
+        assert!(!req("^2.1").matches(&v(2, 0, 9)));
+        assert!(req("^0.3").matches(&v(0, 3, 7)));
+        assert!(!req("^0.3").matches(&v(0, 4, 0)));
+        assert!(!req("^0.0.3").matches(&v(0, 0, 4)));
+        assert!(req("2.1").matches(&v(2, 5, 0))); // Bare version is caret
+        assert!(req("~1.4.2").matches(&v(1, 4, 9)));
+        assert!(!req("~1.4.2").matches(&v(1, 5, 0)));
+        assert!(req("~1").matches(&v(1, 8, 0)));
+    }
+
+    #[test]
+    fn test_multiple_comparators_and_wildcards() {
+        let range = req(">=1.0, <2");
+        assert!(range.matches(&v(1, 5, 0)));
+        assert!(!range.matches(&v(2, 0, 0)));
+        assert_eq!(req(">=1.0 <2"), range);
+        assert!(req("*").matches(&v(0, 0, 1)));
+        assert!(req("v1.x").matches(&v(1, 7, 3)));
+        assert_eq!(range.to_string(), ">=1.0, <2");
+    }
+
+    #[test]
+    fn test_highest_match() {
+        let published = [v(1, 9, 0), v(2, 1, 0), v(2, 4, 3), v(2, 10, 0), v(3, 0, 0)];
+        assert_eq!(req("^2.1").highest_match(&published), Some(v(2, 10, 0)));
+        assert_eq!(req("~2.4").highest_match(&published), Some(v(2, 4, 3)));
+        assert_eq!(req("<2").highest_match(&published), Some(v(1, 9, 0)));
+        assert_eq!(req("^4").highest_match(&published), None);
+    }
+
+    #[test]
+    fn test_bounds_agree_with_matches() {
+        let comparators = ["=1", "=1.2", "=1.2.3", ">1", ">1.2", ">1.2.3", ">=1.2", "<1.2", "<=1", "<=1.2", "<=1.2.3",
+            "~1", "~1.2", "~1.2.3", "^1", "^1.2", "^1.2.3", "^0", "^0.2", "^0.2.3", "^0.0", "^0.0.3", "*"];
+        for comparator in comparators {
+            let (lower, upper) = req(comparator).comparators.first().map_or((v(0, 0, 0), None), Comparator::bounds);
+            for version in (0..4).flat_map(|a| (0..4).flat_map(move |b| (0..5).map(move |c| v(a, b, c)))) {
+                let within = lower <= version && upper.map_or(true, |upper| version < upper);
+                assert_eq!(within, req(comparator).matches(&version), "{} against {}", comparator, version);
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersects() {
+        assert!(req("=3.12").intersects(&req(">=3.12.1")));
+        assert!(req("=20").intersects(&req(">= 18")));
+        assert!(req("^2.1").intersects(&req("~2.4")));
+        assert!(req("*").intersects(&req("<0.0.1")));
+        assert!(!req("=3.11").intersects(&req(">=3.12.1")));
+        assert!(!req("^2").intersects(&req(">=3")));
+        assert!(!req(">=1.0, <2").intersects(&req("=2.0.0")));
+        assert!(!req("<1.2").intersects(&req(">1.1")));
+    }
+
+    #[test]
+    fn test_invalid_requirements() {
+        assert!(VersionReq::parse("").is_err());
+        assert!(VersionReq::parse(">=").is_err());
+        assert!(VersionReq::parse("=>1.0").is_err());
+        assert!(VersionReq::parse(">=a.b").is_err());
+        assert!(VersionReq::parse("1.2.3.4").is_err());
+    }
+}
@@ -0,0 +1,216 @@
+// File: taskline-core/src/versioned.rs
+// --- Versioned script file names (`deploy_v1.2.0.tskln`, `deploy.v1.2.0.tskln`)
+// --- Ordered by Version, never by string: v1.10.0 sorts after v1.9.0
+
+//! Versioned copies of a script, as produced by `taskline-bump`.
+//!
+//! A versioned file name is a base name, a `_vX.Y.Z` or `.vX.Y.Z` suffix and an optional
+//! extension. Bumping replaces the suffix, so a script only ever carries one.
+
+use crate::Version;
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "fs")]
+use crate::TasklineError;
+
+/// The parts of a versioned file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionedName<'a> {
+    /// Name without version suffix and extension
+    pub base: &'a str,
+    /// Version from the suffix
+    pub version: Version,
+    /// Extension without the dot, when present
+    pub extension: Option<&'a str>,
+}
+
+/// Split `deploy_v1.2.0.tskln` into `deploy`, `v1.2.0` and `tskln`; `None` when the name
+/// carries no version
+pub fn parse_file_name(name: &str) -> Option<VersionedName<'_>> {
+    let with_extension = name.rsplit_once('.').and_then(|(stem, extension)| Some((split_version(stem)?, extension)));
+    let (stem, version, extension) = match with_extension {
+        Some(((stem, version), extension)) => (stem, version, Some(extension)),
+        None => {
+            let (stem, version) = split_version(name)?;
+            (stem, version, None)
+        }
+    };
+    (!stem.is_empty()).then_some(VersionedName { base: stem, version, extension })
+}
+
+/// `deploy_v1.2.0.tskln`: the `_vX.Y.Z` form taskline-bump writes
+pub fn file_name(base: &str, version: Version, extension: Option<&str>) -> String {
+    match extension {
+        Some(extension) => format!("{}_{}.{}", base, version, extension),
+        None => format!("{}_{}", base, version),
+    }
+}
+
+/// Strip a trailing `_vX.Y.Z` or `.vX.Y.Z`
+fn split_version(stem: &str) -> Option<(&str, Version)> {
+    stem.rmatch_indices(['_', '.']).find_map(|(i, _)| {
+        let version = Version::parse(stem[i + 1..].strip_prefix('v')?).ok()?;
+        Some((&stem[..i], version))
+    })
+}
+
+/// Match a file name against a pattern where `*` is any run of characters and `?` is one
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A versioned script on disk
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionedFile {
+    /// Path as found
+    pub path: PathBuf,
+    /// Base name shared by all versions of the script
+    pub base: String,
+    /// Version from the file name
+    pub version: Version,
+}
+
+/// Versioned files matching `pattern`, oldest first. Wildcards are allowed in the file
+/// name only (`scripts/deploy*.tskln`); the directory part is taken literally.
+#[cfg(feature = "fs")]
+pub fn find(pattern: &Path) -> Result<Vec<VersionedFile>, TasklineError> {
+    let dir = match pattern.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_pattern = pattern.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let io = |e: std::io::Error| TasklineError::FileError(format!("{}: {}", dir.display(), e));
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io)? {
+        let entry = entry.map_err(io)?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else { continue };
+        if !matches_pattern(&file_pattern, name) || !entry.file_type().map_err(io)?.is_file() {
+            continue;
+        }
+        if let Some(parsed) = parse_file_name(name) {
+            files.push(VersionedFile { path: dir.join(name), base: parsed.base.to_string(), version: parsed.version });
+        }
+    }
+    files.sort_by(|a, b| a.version.cmp(&b.version).then_with(|| a.path.cmp(&b.path)));
+    Ok(files)
+}
+
+/// The highest-versioned file matching `pattern`
+#[cfg(feature = "fs")]
+pub fn latest(pattern: &Path) -> Result<Option<VersionedFile>, TasklineError> {
+    Ok(find(pattern)?.pop())
+}
+
+/// Files of `files` (as returned by [`find`]) beyond the `keep` newest of each base name,
+/// oldest first
+#[cfg(feature = "fs")]
+pub fn outdated(files: &[VersionedFile], keep: usize) -> Vec<&VersionedFile> {
+    let mut newer: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    let mut outdated: Vec<_> = files
+        .iter()
+        .rev()
+        .filter(|file| {
+            let seen = newer.entry(&file.base).or_default();
+            *seen += 1;
+            *seen > keep
+        })
+        .collect();
+    outdated.reverse();
+    outdated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_name() {
+        let parsed = parse_file_name("deploy_v1.10.0.tskln").unwrap();
+        assert_eq!((parsed.base, parsed.version, parsed.extension), ("deploy", Version::new(1, 10, 0), Some("tskln")));
+        let parsed = parse_file_name("deploy.v2.0.1.tskbe").unwrap();
+        assert_eq!((parsed.base, parsed.version), ("deploy", Version::new(2, 0, 1)));
+        let parsed = parse_file_name("build-env_v3.2.1").unwrap();
+        assert_eq!((parsed.base, parsed.version, parsed.extension), ("build-env", Version::new(3, 2, 1), None));
+
+        assert!(parse_file_name("deploy.tskln").is_none());
+        assert!(parse_file_name("deploy_v1.2.tskln").is_none());
+        assert!(parse_file_name("_v1.2.3.tskln").is_none());
+        assert!(parse_file_name("deploy_v+1.2.3.tskln").is_none());
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("deploy", Version::new(1, 10, 0), Some("tskln")), "deploy_v1.10.0.tskln");
+        assert_eq!(file_name("build-env", Version::new(3, 2, 1), None), "build-env_v3.2.1");
+        let parsed = parse_file_name("deploy_v1.10.0.tskln").unwrap();
+        assert_eq!(file_name(parsed.base, parsed.version, parsed.extension), "deploy_v1.10.0.tskln");
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("deploy*", "deploy_v1.0.0.tskln"));
+        assert!(matches_pattern("*_v1.?.0.tskln", "deploy_v1.9.0.tskln"));
+        assert!(matches_pattern("*", ""));
+        assert!(matches_pattern("a*b*c", "aXXbYbZc"));
+        assert!(!matches_pattern("deploy*", "release_v1.0.0.tskln"));
+        assert!(!matches_pattern("*_v1.?.0.tskln", "deploy_v1.10.0.tskln"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_latest_uses_version_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["deploy_v1.9.0.tskln", "deploy_v1.10.0.tskln", "deploy_v1.2.0.tskln", "deploy.tskln", "other_v9.0.0.tskln"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("deploy_v9.9.9.tskln")).unwrap();
+
+        let found = find(&dir.path().join("deploy*")).unwrap();
+        let versions: Vec<_> = found.iter().map(|f| f.version.to_string()).collect();
+        assert_eq!(versions, ["v1.2.0", "v1.9.0", "v1.10.0"]);
+        assert_eq!(latest(&dir.path().join("deploy*")).unwrap().unwrap().path, dir.path().join("deploy_v1.10.0.tskln"));
+        assert_eq!(latest(&dir.path().join("*.tskln")).unwrap().unwrap().base, "other");
+        assert!(latest(&dir.path().join("missing*")).unwrap().is_none());
+        assert!(find(&dir.path().join("nowhere").join("*")).is_err());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_outdated_keeps_newest_per_base() {
+        let file = |base: &str, minor| VersionedFile {
+            path: PathBuf::from(format!("{}_v1.{}.0.tskln", base, minor)),
+            base: base.to_string(),
+            version: Version::new(1, minor, 0),
+        };
+        let files = [file("a", 1), file("b", 1), file("a", 2), file("a", 9), file("a", 10)];
+        let names = |keep| outdated(&files, keep).iter().map(|f| f.path.display().to_string()).collect::<Vec<_>>();
+        assert_eq!(names(2), ["a_v1.1.0.tskln", "a_v1.2.0.tskln"]);
+        assert_eq!(names(1), ["a_v1.1.0.tskln", "a_v1.2.0.tskln", "a_v1.9.0.tskln"]);
+        assert!(names(5).is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+// File: taskline-core/src/wasm.rs
+// --- JavaScript bindings for the browser playground (feature "wasm")
+// --- Build: cargo build -p taskline-core --target wasm32-unknown-unknown --no-default-features --features wasm
+
+//! `wasm-bindgen` exports: `parse`, `validate` and `format`.
+
+use wasm_bindgen::prelude::*;
+use crate::TasklineMetadata;
+
+/// Parsed header as seen from JavaScript
+#[wasm_bindgen]
+pub struct Header {
+    inner: TasklineMetadata,
+}
+
+#[wasm_bindgen]
+impl Header {
+    /// `@Taskline codename`
+    #[wasm_bindgen(getter)]
+    pub fn codename(&self) -> String {
+        self.inner.codename.clone()
+    }
+
+    /// `@Taskline version` as `vX.Y.Z`, or `undefined`
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> Option<String> {
+        self.inner.version.map(|v| v.to_string())
+    }
+
+    /// `@Taskline author` entries as `Name <email>`
+    #[wasm_bindgen(getter)]
+    pub fn authors(&self) -> Vec<String> {
+        self.inner.authors.iter().map(|a| a.to_string()).collect()
+    }
+
+    /// `@Taskline created`, or `undefined`
+    #[wasm_bindgen(getter)]
+    pub fn created(&self) -> Option<String> {
+        self.inner.created.clone()
+    }
+
+    /// `@Taskline expires` date, or `undefined`
+    #[wasm_bindgen(getter)]
+    pub fn expires(&self) -> Option<String> {
+        self.inner.expires.clone()
+    }
+
+    /// `@Taskline license`, or `undefined`
+    #[wasm_bindgen(getter)]
+    pub fn license(&self) -> Option<String> {
+        self.inner.license.clone()
+    }
+
+    /// `@Taskline deprecated` message (`""` when none was given), or `undefined`
+    #[wasm_bindgen(getter)]
+    pub fn deprecated(&self) -> Option<String> {
+        self.inner.deprecated.clone()
+    }
+
+    /// `@Taskline path` entries
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> Vec<String> {
+        self.inner.path.clone()
+    }
+
+    /// Raw `@Taskline requires` values
+    #[wasm_bindgen(getter)]
+    pub fn requires(&self) -> Vec<String> {
+        self.inner.requires.clone()
+    }
+
+    /// Raw `@Taskline requires-taskline` value, or `undefined`
+    #[wasm_bindgen(getter = requiresTaskline)]
+    pub fn requires_taskline(&self) -> Option<String> {
+        self.inner.requires_taskline.clone()
+    }
+
+    /// Render the header block
+    #[wasm_bindgen(js_name = toHeader)]
+    pub fn to_header(&self) -> String {
+        self.inner.to_header()
+    }
+}
+
+/// Parse a script leniently
+#[wasm_bindgen]
+pub fn parse(content: &str) -> Header {
+    Header { inner: TasklineMetadata::parse(content) }
+}
+
+/// Parse a script, throwing on a missing codename, invalid version or bad `requires`
+#[wasm_bindgen]
+pub fn validate(content: &str) -> Result<Header, JsError> {
+    TasklineMetadata::validate(content)
+        .map(|inner| Header { inner })
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Canonically re-render the header, throwing when it is invalid
+#[wasm_bindgen]
+pub fn format(content: &str) -> Result<String, JsError> {
+    crate::format(content).map_err(|e| JsError::new(&e.to_string()))
+}
@@ -0,0 +1,221 @@
+// File: taskline-core/src/workspace.rs
+// --- Scripts of a workspace and the references between them
+// --- Used by `taskline validate --all` and by taskline-bump to follow renames
+
+//! Workspace-wide script discovery and cross-reference rewriting.
+//!
+//! A reference is any mention of a script's path in another script or in
+//! `.taskline.toml`, such as `source ./lib/common_v1.2.0.tskln`. A reference counts only
+//! if it resolves to the renamed file, from the referring file's directory or from the
+//! workspace root, so a script with the same name in another directory is left alone.
+
+#[cfg(feature = "fs")]
+use std::path::{Component, Path, PathBuf};
+#[cfg(feature = "fs")]
+use crate::config::CONFIG_FILE_NAME;
+#[cfg(feature = "fs")]
+use crate::constants::TASKLINE_EXTENSION;
+#[cfg(feature = "fs")]
+use crate::TasklineError;
+
+/// Directories never searched for scripts
+pub const SKIPPED_DIRS: [&str; 2] = ["target", "node_modules"];
+
+/// All `.tskln` files under `root`, sorted. Hidden directories, `target` and
+/// `node_modules` are skipped.
+#[cfg(feature = "fs")]
+pub fn find_scripts(root: &Path) -> Result<Vec<PathBuf>, TasklineError> {
+    let mut scripts = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let io = |e: std::io::Error| TasklineError::FileError(format!("{}: {}", dir.display(), e));
+        for entry in std::fs::read_dir(&dir).map_err(io)? {
+            let entry = entry.map_err(io)?;
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if entry.file_type().map_err(io)?.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                    pending.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == TASKLINE_EXTENSION) {
+                scripts.push(path);
+            }
+        }
+    }
+    scripts.sort();
+    Ok(scripts)
+}
+
+/// Replace every path in `content` whose file name is `old_name` and for which `resolves`
+/// returns true with the same path ending in `new_name`. `None` when nothing changed.
+pub fn rewrite_references(content: &str, old_name: &str, new_name: &str, resolves: impl Fn(&str) -> bool) -> Option<String> {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.');
+    let is_path_char = |c: char| is_name_char(c) || matches!(c, '/' | '\\');
+    let mut rewritten = String::with_capacity(content.len());
+    let mut copied = 0;
+    for (at, _) in content.match_indices(old_name) {
+        let end = at + old_name.len();
+        let before = content[..at].chars().next_back();
+        if before.is_some_and(is_name_char) || content[end..].chars().next().is_some_and(is_name_char) {
+            continue;
+        }
+        let start = content[..at].char_indices().rev().find(|&(_, c)| !is_path_char(c)).map_or(0, |(i, c)| i + c.len_utf8());
+        if !resolves(&content[start..end]) {
+            continue;
+        }
+        rewritten.push_str(&content[copied..at]);
+        rewritten.push_str(new_name);
+        copied = end;
+    }
+    if copied == 0 {
+        return None;
+    }
+    rewritten.push_str(&content[copied..]);
+    Some(rewritten)
+}
+
+/// Rewrite references to `old` (a file that was just renamed to `new`, in the same
+/// directory) in every script under `root` and in `root/.taskline.toml`. Returns the
+/// files that changed.
+#[cfg(feature = "fs")]
+pub fn update_references(root: &Path, old: &Path, new: &Path) -> Result<Vec<PathBuf>, TasklineError> {
+    let io = |path: &Path, e: std::io::Error| TasklineError::FileError(format!("{}: {}", path.display(), e));
+    let names = old.file_name().and_then(|n| n.to_str()).zip(new.file_name().and_then(|n| n.to_str()));
+    let Some((old_name, new_name)) = names else { return Ok(Vec::new()) };
+    let root = canonicalize(root).map_err(|e| io(root, e))?;
+    let old = match old.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => canonicalize(dir).map_err(|e| io(dir, e))?.join(old_name),
+        None => std::env::current_dir().map_err(|e| io(Path::new("."), e))?.join(old_name),
+    };
+
+    let mut files = find_scripts(&root)?;
+    let config = root.join(CONFIG_FILE_NAME);
+    if config.is_file() {
+        files.push(config);
+    }
+    let mut changed = Vec::new();
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(&file) else { continue };
+        let dir = file.parent().unwrap_or(&root);
+        let resolves = |reference: &str| {
+            let reference = Path::new(reference);
+            normalize(&dir.join(reference)) == old || normalize(&root.join(reference)) == old
+        };
+        if let Some(rewritten) = rewrite_references(&content, old_name, new_name, resolves) {
+            std::fs::write(&file, rewritten).map_err(|e| io(&file, e))?;
+            changed.push(file);
+        }
+    }
+    Ok(changed)
+}
+
+/// [`std::fs::canonicalize`], minus the `\\?\` prefix Windows puts on ordinary drive paths
+/// (`\\?\C:\repo` becomes `C:\repo`), so the result reads normally and `cmd.exe` accepts
+/// it as a working directory. Network (`\\?\UNC\...`) paths keep the prefix.
+#[cfg(feature = "fs")]
+pub fn canonicalize(path: &Path) -> std::io::Result<PathBuf> {
+    Ok(strip_verbatim(std::fs::canonicalize(path)?))
+}
+
+/// Create `target` with `content`, failing instead of overwriting when it already exists.
+/// `action` names the aborted operation in that error (`"Pull"`, `"Decryption"`).
+#[cfg(feature = "fs")]
+pub fn write_new_file(target: &Path, content: &[u8], action: &str) -> Result<(), TasklineError> {
+    use std::io::Write;
+    let file = std::fs::OpenOptions::new().write(true).create_new(true).open(target);
+    let mut file = match file {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            return Err(TasklineError::FileError(format!(
+                "'{}' already exists. {} aborted to prevent overwriting.", target.display(), action
+            )));
+        }
+        Err(e) => return Err(TasklineError::FileError(format!("{}: {}", target.display(), e))),
+    };
+    file.write_all(content).map_err(|e| TasklineError::FileError(format!("{}: {}", target.display(), e)))
+}
+
+#[cfg(feature = "fs")]
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let text = path.to_string_lossy();
+    match text.strip_prefix(r"\\?\") {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') && rest.as_bytes()[0].is_ascii_alphabetic() => PathBuf::from(rest),
+        _ => path,
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+#[cfg(feature = "fs")]
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_references() {
+        let content = "source ./lib/util_v1.0.0.tskln\nrun util_v1.0.0.tskln && util_v1.0.0.tskln.bak my-util_v1.0.0.tskln\n";
+        let rewritten = rewrite_references(content, "util_v1.0.0.tskln", "util_v1.0.1.tskln", |path| path != "util_v1.0.0.tskln").unwrap();
+        assert_eq!(rewritten, "source ./lib/util_v1.0.1.tskln\nrun util_v1.0.0.tskln && util_v1.0.0.tskln.bak my-util_v1.0.0.tskln\n");
+        assert!(rewrite_references(content, "util_v1.0.0.tskln", "x", |_| false).is_none());
+        assert_eq!(rewrite_references("é\"a.tskln\"", "a.tskln", "b.tskln", |path| path == "a.tskln").unwrap(), "é\"b.tskln\"");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(strip_verbatim(PathBuf::from(r"\\?\C:\repo\a.tskln")), PathBuf::from(r"C:\repo\a.tskln"));
+        assert_eq!(strip_verbatim(PathBuf::from(r"\\?\UNC\server\share")), PathBuf::from(r"\\?\UNC\server\share"));
+        assert_eq!(strip_verbatim(PathBuf::from("/home/a.tskln")), PathBuf::from("/home/a.tskln"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_write_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("deploy_v1.0.0.tskln");
+        write_new_file(&target, b"first", "Pull").unwrap();
+        let error = write_new_file(&target, b"second", "Pull").unwrap_err();
+        assert!(error.to_string().contains("Pull aborted to prevent overwriting"));
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "first");
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_update_references() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = root.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("lib/common_v1.0.1.tskln", "renamed already\n");
+        write("app/main.tskln", "source ../lib/common_v1.0.0.tskln\n");
+        write("other.tskln", "source lib/common_v1.0.0.tskln\nsource vendor/common_v1.0.0.tskln\n");
+        write("vendor/common_v1.0.0.tskln", "a different script\n");
+        write(".taskline.toml", "[hooks]\npost-bump = [\"taskline doctor lib/common_v1.0.0.tskln\"]\n");
+
+        let old = root.path().join("lib/common_v1.0.0.tskln");
+        let new = root.path().join("lib/common_v1.0.1.tskln");
+        let mut changed = update_references(root.path(), &old, &new).unwrap();
+        changed.sort();
+        assert_eq!(changed.len(), 3);
+        let read = |path: &str| std::fs::read_to_string(root.path().join(path)).unwrap();
+        assert_eq!(read("app/main.tskln"), "source ../lib/common_v1.0.1.tskln\n");
+        assert_eq!(read("other.tskln"), "source lib/common_v1.0.1.tskln\nsource vendor/common_v1.0.0.tskln\n");
+        assert!(read(".taskline.toml").contains("doctor lib/common_v1.0.1.tskln"));
+    }
+}
@@ -0,0 +1,136 @@
+#ifndef TASKLINE_H
+#define TASKLINE_H
+
+/* Generated by cbindgen from taskline-ffi. Do not edit by hand; run `UPDATE_TASKLINE_HEADER=1 cargo test -p taskline-ffi`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of a fallible call
+ */
+typedef enum TasklineStatus {
+  /**
+   * Success
+   */
+  TASKLINE_STATUS_OK = 0,
+  /**
+   * A required pointer argument was NULL
+   */
+  TASKLINE_STATUS_NULL_ARGUMENT = 1,
+  /**
+   * A string argument or file was not valid UTF-8
+   */
+  TASKLINE_STATUS_INVALID_UTF8 = 2,
+  /**
+   * The file could not be read
+   */
+  TASKLINE_STATUS_IO_ERROR = 3,
+  /**
+   * The header is malformed
+   */
+  TASKLINE_STATUS_PARSE_ERROR = 4,
+  /**
+   * A version is malformed
+   */
+  TASKLINE_STATUS_VERSION_ERROR = 5,
+  /**
+   * Any other failure
+   */
+  TASKLINE_STATUS_OTHER_ERROR = 6,
+} TasklineStatus;
+
+/**
+ * Parsed `@Taskline` header; opaque to C
+ */
+typedef struct TasklineHeader TasklineHeader;
+
+/**
+ * A `vX.Y.Z` version
+ */
+typedef struct TasklineVersion {
+  /**
+   * Major component
+   */
+  uint32_t major;
+  /**
+   * Minor component
+   */
+  uint32_t minor;
+  /**
+   * Patch component
+   */
+  uint32_t patch;
+} TasklineVersion;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parse a `vX.Y.Z` version into `out`.
+ *
+ * # Safety
+ * `input` must be NULL or a valid NUL-terminated string; `out` must be NULL or writable.
+ */
+enum TasklineStatus taskline_version_parse(const char *input, struct TasklineVersion *out);
+
+/**
+ * Parse the header of script content. Returns NULL when `content` is NULL or not UTF-8.
+ * Free the result with `taskline_header_free`.
+ *
+ * # Safety
+ * `content` must be NULL or a valid NUL-terminated string.
+ */
+struct TasklineHeader *taskline_header_parse(const char *content);
+
+/**
+ * Codename of a parsed header (empty when missing). The string is owned by `header`.
+ *
+ * # Safety
+ * `header` must be NULL or a pointer returned by `taskline_header_parse` that was not freed.
+ */
+const char *taskline_header_codename(const struct TasklineHeader *header);
+
+/**
+ * Copy the header's version into `out`; returns false when the header has no valid version.
+ *
+ * # Safety
+ * `header` must be NULL or a live pointer from `taskline_header_parse`; `out` must be NULL or writable.
+ */
+bool taskline_header_version(const struct TasklineHeader *header,
+                             struct TasklineVersion *out);
+
+/**
+ * Release a header returned by `taskline_header_parse`. NULL is ignored.
+ *
+ * # Safety
+ * `header` must be NULL or a pointer from `taskline_header_parse` that was not already freed.
+ */
+void taskline_header_free(struct TasklineHeader *header);
+
+/**
+ * Read and validate a `.tskln` file. On failure, and when `error_out` is not NULL, a message
+ * is stored there; release it with `taskline_string_free`.
+ *
+ * # Safety
+ * `path` must be NULL or a valid NUL-terminated string; `error_out` must be NULL or writable.
+ */
+enum TasklineStatus taskline_validate_file(const char *path, char **error_out);
+
+/**
+ * Release a string returned by this library. NULL is ignored.
+ *
+ * # Safety
+ * `s` must be NULL or a string returned by this library that was not already freed.
+ */
+void taskline_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TASKLINE_H */
@@ -0,0 +1,283 @@
+// File: taskline-ffi/src/lib.rs
+// --- C interface to taskline-core for existing C/C++ build tooling
+// --- Declarations are mirrored in include/taskline.h (generated by cbindgen)
+
+//! `extern "C"` bindings over [`taskline_core`].
+//!
+//! Strings passed in must be NUL-terminated UTF-8. Objects and strings returned by this
+//! library are owned by the caller and released with the matching `*_free` function.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use taskline_core::{TasklineError, TasklineMetadata, Version};
+
+/// Result of a fallible call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TasklineStatus {
+    /// Success
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullArgument = 1,
+    /// A string argument or file was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// The file could not be read
+    IoError = 3,
+    /// The header is malformed
+    ParseError = 4,
+    /// A version is malformed
+    VersionError = 5,
+    /// Any other failure
+    OtherError = 6,
+}
+
+/// A `vX.Y.Z` version
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TasklineVersion {
+    /// Major component
+    pub major: u32,
+    /// Minor component
+    pub minor: u32,
+    /// Patch component
+    pub patch: u32,
+}
+
+/// Parsed `@Taskline` header; opaque to C
+pub struct TasklineHeader {
+    metadata: TasklineMetadata,
+    codename: CString,
+}
+
+impl From<Version> for TasklineVersion {
+    fn from(v: Version) -> Self {
+        Self { major: v.major, minor: v.minor, patch: v.patch }
+    }
+}
+
+fn status_of(error: &TasklineError) -> TasklineStatus {
+    match error {
+        TasklineError::ParseError(_) => TasklineStatus::ParseError,
+        TasklineError::VersionError(_) => TasklineStatus::VersionError,
+        TasklineError::FileError(_) => TasklineStatus::IoError,
+        _ => TasklineStatus::OtherError,
+    }
+}
+
+/// Borrow a C string as UTF-8
+unsafe fn to_str<'a>(s: *const c_char) -> Result<&'a str, TasklineStatus> {
+    if s.is_null() {
+        return Err(TasklineStatus::NullArgument);
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| TasklineStatus::InvalidUtf8)
+}
+
+/// Hand an error message to the caller through `error_out`, when provided
+unsafe fn report(error_out: *mut *mut c_char, message: String) {
+    if !error_out.is_null() {
+        *error_out = CString::new(message.replace('\0', " ")).map_or(ptr::null_mut(), CString::into_raw);
+    }
+}
+
+/// Parse a `vX.Y.Z` version into `out`.
+///
+/// # Safety
+/// `input` must be NULL or a valid NUL-terminated string; `out` must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_version_parse(input: *const c_char, out: *mut TasklineVersion) -> TasklineStatus {
+    if out.is_null() {
+        return TasklineStatus::NullArgument;
+    }
+    let input = match to_str(input) {
+        Ok(input) => input,
+        Err(status) => return status,
+    };
+    match Version::parse(input) {
+        Ok(version) => {
+            *out = version.into();
+            TasklineStatus::Ok
+        }
+        Err(e) => status_of(&e),
+    }
+}
+
+/// Parse the header of script content. Returns NULL when `content` is NULL or not UTF-8.
+/// Free the result with `taskline_header_free`.
+///
+/// # Safety
+/// `content` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_header_parse(content: *const c_char) -> *mut TasklineHeader {
+    let Ok(content) = to_str(content) else { return ptr::null_mut() };
+    let metadata = TasklineMetadata::parse(content);
+    let codename = CString::new(metadata.codename.replace('\0', "")).unwrap_or_default();
+    Box::into_raw(Box::new(TasklineHeader { metadata, codename }))
+}
+
+/// Codename of a parsed header (empty when missing). The string is owned by `header`.
+///
+/// # Safety
+/// `header` must be NULL or a pointer returned by `taskline_header_parse` that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_header_codename(header: *const TasklineHeader) -> *const c_char {
+    match header.as_ref() {
+        Some(header) => header.codename.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Copy the header's version into `out`; returns false when the header has no valid version.
+///
+/// # Safety
+/// `header` must be NULL or a live pointer from `taskline_header_parse`; `out` must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_header_version(header: *const TasklineHeader, out: *mut TasklineVersion) -> bool {
+    match (header.as_ref(), out.is_null()) {
+        (Some(TasklineHeader { metadata: TasklineMetadata { version: Some(version), .. }, .. }), false) => {
+            *out = (*version).into();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Release a header returned by `taskline_header_parse`. NULL is ignored.
+///
+/// # Safety
+/// `header` must be NULL or a pointer from `taskline_header_parse` that was not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_header_free(header: *mut TasklineHeader) {
+    if !header.is_null() {
+        drop(Box::from_raw(header));
+    }
+}
+
+/// Read and validate a `.tskln` file. On failure, and when `error_out` is not NULL, a message
+/// is stored there; release it with `taskline_string_free`.
+///
+/// # Safety
+/// `path` must be NULL or a valid NUL-terminated string; `error_out` must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_validate_file(path: *const c_char, error_out: *mut *mut c_char) -> TasklineStatus {
+    if !error_out.is_null() {
+        *error_out = ptr::null_mut();
+    }
+    let path = match to_str(path) {
+        Ok(path) => path,
+        Err(status) => return status,
+    };
+    let content = match std::fs::read(path) {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(_) => {
+                report(error_out, format!("{}: not valid UTF-8", path));
+                return TasklineStatus::InvalidUtf8;
+            }
+        },
+        Err(e) => {
+            report(error_out, format!("{}: {}", path, e));
+            return TasklineStatus::IoError;
+        }
+    };
+    match TasklineMetadata::validate(&content) {
+        Ok(_) => TasklineStatus::Ok,
+        Err(e) => {
+            report(error_out, format!("{}: {}", path, e));
+            status_of(&e)
+        }
+    }
+}
+
+/// Release a string returned by this library. NULL is ignored.
+///
+/// # Safety
+/// `s` must be NULL or a string returned by this library that was not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn taskline_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn cstr(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_version_parse() {
+        let mut out = TasklineVersion { major: 0, minor: 0, patch: 0 };
+        unsafe {
+            assert_eq!(taskline_version_parse(cstr("v1.2.3").as_ptr(), &mut out), TasklineStatus::Ok);
+            assert_eq!(out, TasklineVersion { major: 1, minor: 2, patch: 3 });
+            assert_eq!(taskline_version_parse(cstr("1.2").as_ptr(), &mut out), TasklineStatus::VersionError);
+            assert_eq!(taskline_version_parse(ptr::null(), &mut out), TasklineStatus::NullArgument);
+        }
+    }
+
+    #[test]
+    fn test_header_round_trip() {
+        unsafe {
+            let header = taskline_header_parse(cstr("@Taskline codename deploy\n@Taskline version v2.0.1\n\n").as_ptr());
+            assert!(!header.is_null());
+            assert_eq!(CStr::from_ptr(taskline_header_codename(header)).to_str().unwrap(), "deploy");
+            let mut version = TasklineVersion { major: 0, minor: 0, patch: 0 };
+            assert!(taskline_header_version(header, &mut version));
+            assert_eq!(version, TasklineVersion { major: 2, minor: 0, patch: 1 });
+            taskline_header_free(header);
+
+            let bare = taskline_header_parse(cstr("@Taskline codename bare\n").as_ptr());
+            assert!(!taskline_header_version(bare, &mut version));
+            taskline_header_free(bare);
+            assert!(taskline_header_parse(ptr::null()).is_null());
+        }
+    }
+
+    #[test]
+    fn test_validate_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let good = dir.join("good.tskln");
+        let bad = dir.join("bad.tskln");
+        std::fs::write(&good, "@Taskline codename good\n@Taskline version v1.0.0\n\n").unwrap();
+        std::fs::write(&bad, "@Taskline codename bad\n@Taskline version 1.0\n\n").unwrap();
+
+        unsafe {
+            let path = |p: &Path| CString::new(p.to_str().unwrap()).unwrap();
+            let mut error = ptr::null_mut();
+            assert_eq!(taskline_validate_file(path(&good).as_ptr(), &mut error), TasklineStatus::Ok);
+            assert!(error.is_null());
+
+            assert_eq!(taskline_validate_file(path(&bad).as_ptr(), &mut error), TasklineStatus::VersionError);
+            assert!(CStr::from_ptr(error).to_str().unwrap().contains("bad.tskln"));
+            taskline_string_free(error);
+
+            let missing = dir.join("missing.tskln");
+            assert_eq!(taskline_validate_file(path(&missing).as_ptr(), ptr::null_mut()), TasklineStatus::IoError);
+        }
+    }
+
+    #[test]
+    fn test_header_file_is_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+        let mut generated = Vec::new();
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(root.join("src/lib.rs"))
+            .generate()
+            .expect("cbindgen failed")
+            .write(&mut generated);
+
+        let header_path = root.join("include/taskline.h");
+        if std::env::var_os("UPDATE_TASKLINE_HEADER").is_some() {
+            std::fs::write(&header_path, &generated).unwrap();
+        }
+        let committed = std::fs::read(&header_path).unwrap_or_default();
+        assert!(committed == generated, "include/taskline.h is stale; run `UPDATE_TASKLINE_HEADER=1 cargo test -p taskline-ffi`");
+    }
+}
@@ -2,9 +2,11 @@
 // --- Ultra-fast Taskline initialization with minimal dependencies
 // --- Optimized for maximum performance and minimal binary size
 
+mod template;
+
 use std::io::Write;
 use chrono::prelude::*;
-use tokio::io::AsyncWriteExt;
+use template::TemplateVars;
 
 #[derive(Debug)]
 struct TasklineInitializationError {
@@ -17,100 +19,110 @@ impl std::fmt::Display for TasklineInitializationError {
     }
 }
 
-async fn validate_version(version: &str) -> Result<(), TasklineInitializationError> {
+/// Validate a version argument; `1.2.3` and `v1.2.3` are both accepted
+fn validate_version(version: &str) -> Result<taskline_core::Version, TasklineInitializationError> {
     log::trace!("Starting version validation for: '{}'", version);
-    
-    // Ultra-fast version validation using direct byte access
-    let bytes = version.as_bytes();
-    let len = bytes.len();
-
-    log::debug!("Version string length: {} bytes", len);
 
-    // Fast early return for empty or too short versions
-    if len < 5 {  // Minimum: "v1.0" (4 chars) but we need at least one more for patch
-        log::warn!("Version too short: '{}' (length: {}), minimum format is v1.0.0", version, len);
-        return Err(TasklineInitializationError {
-            details: "Version too short - minimum format is v1.0.0".to_string()
-        });
+    // Same rules as every other Taskline tool: the core parser is the single source of truth
+    match taskline_core::Version::parse(version) {
+        Ok(parsed) => {
+            log::info!("Version validation successful for '{}' ({})", version, parsed);
+            Ok(parsed)
+        }
+        Err(e) => {
+            log::error!("Invalid version '{}': {}", version, e);
+            Err(TasklineInitializationError { details: e.to_string() })
+        }
     }
+}
 
-    // Check first byte without bounds checking (we know len >= 5)
-    if unsafe { *bytes.get_unchecked(0) } != b'v' {
-        log::error!("Version does not start with 'v': '{}'", version);
-        return Err(TasklineInitializationError {
-            details: "Version must start with 'v'".to_string()
-        });
+/// Enforce the `[naming]` rules of the nearest `.taskline.toml`, if any
+fn check_naming_policy(codename: &str) -> Result<(), taskline_core::TasklineError> {
+    let cwd = std::env::current_dir().map_err(|e| taskline_core::TasklineError::FileError(e.to_string()))?;
+    match taskline_core::config::TasklineConfig::discover(&cwd)? {
+        Some(loaded) => loaded.config.naming.check_codename(codename),
+        None => Ok(()),
     }
+}
 
-    log::trace!("Version starts with 'v' - continuing validation");
+const USAGE: &str = "Usage: taskline.init <filename> [version] [--author <name>] [--email <email>] [--template <file>] [--var key=value]...";
 
-    let mut dot_count = 0u8;
-    let mut has_digits_in_current_part = false;
-    let mut i = 1;
+fn git_config(key: &str) -> Option<String> {
+    std::process::Command::new("git")
+        .args(["config", key])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|value| !value.is_empty())
+}
 
-    // Manual loop is faster than iterator combinators
-    while i < len {
-        let byte = unsafe { *bytes.get_unchecked(i) };
-        
-        match byte {
-            b'.' => {
-                if !has_digits_in_current_part {
-                    log::error!("Empty version part found at position {} in '{}'", i, version);
-                    return Err(TasklineInitializationError {
-                        details: "Empty version part - each part must contain digits".to_string()
-                    });
-                }
-                
-                dot_count += 1;
-                log::trace!("Found dot #{} at position {}", dot_count, i);
-                
-                if dot_count > 2 {
-                    log::error!("Too many dots ({}) in version '{}'", dot_count, version);
-                    return Err(TasklineInitializationError {
-                        details: "Too many dots - version must have exactly 2 dots (major.minor.patch)".to_string()
-                    });
-                }
-                
-                has_digits_in_current_part = false;
-            }
-            b'0'..=b'9' => {
-                has_digits_in_current_part = true;
-                log::trace!("Found digit '{}' at position {}", byte as char, i);
-            }
-            _ => {
-                log::error!("Invalid character '{}' at position {} in version '{}'", byte as char, i, version);
-                return Err(TasklineInitializationError {
-                    details: "Invalid character - version can only contain digits and dots".to_string()
-                });
-            }
-        }
-        
-        i += 1;
+/// `@Taskline author` value: `--author`/`--email` override git's `user.name`/`user.email`,
+/// and the login name stands in when git has no name configured
+fn resolve_author(name: Option<&String>, email: Option<&String>) -> Option<String> {
+    let name = name.cloned()
+        .or_else(|| git_config("user.name"))
+        .or_else(|| std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok());
+    let email = email.cloned().or_else(|| git_config("user.email"));
+    match (name, email) {
+        (Some(name), Some(email)) => Some(format!("{} <{}>", name, email)),
+        (Some(name), None) => Some(name),
+        (None, Some(email)) => Some(format!("<{}>", email)),
+        (None, None) => None,
     }
+}
 
-    // Final validation - use bitwise operations for faster comparison
-    if dot_count != 2 {
-        log::error!("Incorrect number of dots: {} (expected 2) in version '{}'", dot_count, version);
-        return Err(TasklineInitializationError {
-            details: "Missing dots - version must have exactly 2 dots (major.minor.patch)".to_string()
-        });
+/// Render a template file with the built-in variables and `--var` overrides.
+/// Templates without their own `@Taskline codename` line get the standard header prepended.
+fn render_template(
+    path: &str,
+    codename: &str,
+    version: Option<&String>,
+    author: Option<&str>,
+    created: &str,
+    assignments: &[&String],
+    header: String,
+) -> Result<String, String> {
+    let template = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+
+    let mut vars = TemplateVars::new();
+    vars.set("codename", codename);
+    if let Some(ver) = version {
+        vars.set("version", ver.as_str());
+    }
+    if let Some(author) = author {
+        vars.set("author", author);
+    }
+    vars.set("date", created);
+    for assignment in assignments {
+        vars.set_assignment(assignment)?;
     }
 
-    if !has_digits_in_current_part {
-        log::error!("Last version part is empty in '{}'", version);
-        return Err(TasklineInitializationError {
-            details: "Last version part is empty - must contain digits".to_string()
-        });
+    let rendered = template::render(&template, &vars)?;
+    log::debug!("Rendered template '{}' ({} bytes)", path, rendered.len());
+    if taskline_core::TasklineMetadata::parse(&rendered).codename.is_empty() {
+        // Match a CRLF template's line endings
+        Ok(taskline_core::LineEnding::detect(&rendered).apply(&header) + &rendered)
+    } else {
+        Ok(rendered)
     }
+}
 
-    log::info!("Version validation successful for '{}'", version);
-    Ok(())
+// Runs on the main thread with std I/O: no async runtime to link or start for writing
+// one small file
+fn main() {
+    run();
 }
 
-#[tokio::main(flavor="multi_thread")]
-async fn main() {
+fn run() {
+    taskline_core::crash::install_panic_hook("taskline-init", env!("CARGO_PKG_VERSION"));
+    // CI logs are diffed between runs: warnings only, and no wall-clock time
+    let ci = taskline_core::ci::detect();
     env_logger::Builder::new()
-        .format(|buf, record| {
+        .format(move |buf, record| {
+            if ci {
+                return writeln!(buf, "[{}] - {}", record.level(), record.args());
+            }
             // Get current UTC time and convert to IST (UTC+5:30)
             let now_utc = Utc::now();
             let ist_offset = chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
@@ -123,7 +135,7 @@ async fn main() {
             record.args()
             )
         })
-        .filter_level(log::LevelFilter::Trace)
+        .filter_level(if ci { log::LevelFilter::Warn } else { log::LevelFilter::Trace })
         .filter(Some("serial_test"), log::LevelFilter::Error)
         .filter(Some("tokio_tungstenite"), log::LevelFilter::Error)
         .filter(Some("tungstenite"), log::LevelFilter::Error)
@@ -140,36 +152,78 @@ async fn main() {
     let arguments: Vec<String> = std::env::args().collect();
     log::debug!("Command line arguments: {:?}", arguments);
     
-    // Expecting exactly 2 arguments: program name, filename
-    // or 3 arguments: program name, filename, version
-    let argument_length = arguments.len();
-    log::trace!("Argument count: {}", argument_length);
+    // Positionals: filename [version]; options: --template <file>, --var key=value (repeatable)
+    let mut positionals: Vec<&String> = Vec::with_capacity(2);
+    let mut template_path: Option<&String> = None;
+    let mut author_name: Option<&String> = None;
+    let mut author_email: Option<&String> = None;
+    let mut assignments: Vec<&String> = Vec::new();
+    let mut args = arguments.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--template" | "--var" | "--author" | "--email" => {
+                let Some(value) = args.next() else {
+                    eprintln!("Missing value for {}", arg);
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                };
+                match arg.as_str() {
+                    "--template" => template_path = Some(value),
+                    "--author" => author_name = Some(value),
+                    "--email" => author_email = Some(value),
+                    _ => assignments.push(value),
+                }
+            }
+            _ => positionals.push(arg),
+        }
+    }
     
-    if argument_length < 2 || argument_length > 3 {
-        log::error!("Invalid argument count: {} (expected 2 or 3)", argument_length);
-        eprintln!("Usage: taskline.init <filename> [version]");
+    let argument_length = positionals.len();
+    log::trace!("Positional argument count: {}", argument_length);
+    
+    if !(1..=2).contains(&argument_length) {
+        log::error!("Invalid argument count: {} (expected 1 or 2)", argument_length);
+        eprintln!("{}", USAGE);
         std::process::exit(1);
     }
 
-    let filename = &arguments[1];
-    let version = if argument_length == 3 {
-        Some(&arguments[2])
-    } else {
-        None
-    };
+    let filename = positionals[0];
+
+    // The codename becomes part of the file name, so it must be filesystem-safe
+    if let Err(e) = taskline_core::Codename::parse(filename) {
+        log::error!("Codename validation failed: {}", e);
+        eprintln!("{}", e);
+        if let Ok(suggestion) = taskline_core::Codename::sanitize(filename) {
+            eprintln!("Try '{}' instead.", suggestion);
+        }
+        std::process::exit(1);
+    }
+    if let Err(e) = check_naming_policy(filename) {
+        log::error!("Naming policy check failed: {}", e);
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
 
-    log::info!("Initializing file: '{}' with version: {:?}", filename, version);
+    let requested_version = positionals.get(1).copied();
 
+    log::info!("Initializing file: '{}' with version: {:?}", filename, requested_version);
+
+    // The version is always written in its canonical `vX.Y.Z` form
+    let mut version = None;
     let filename_with_extension: String;
-    if let Some(ver) = version {
+    if let Some(ver) = requested_version {
         log::debug!("Version provided: '{}' - validating", ver);
-        if let Err(e) = validate_version(ver).await {
-            log::error!("Version validation failed: {}", e);
-            eprintln!("{}", e);
-            std::process::exit(1);
-        }
+        let canonical = match validate_version(ver) {
+            Ok(parsed) => parsed.to_string(),
+            Err(e) => {
+                log::error!("Version validation failed: {}", e);
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        };
         log::info!("Version validation passed for '{}'", ver);
-        filename_with_extension = format!("{}.{}.tskln", filename, ver);
+        filename_with_extension = format!("{}.{}.tskln", filename, canonical);
+        version = Some(canonical);
     } else {
         log::debug!("No version provided - using default naming");
         filename_with_extension = format!("{}.tskln", filename);
@@ -183,9 +237,41 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // The following content has to be written to the file
+    // @Taskline codename {filename}
+    // @Taskline version {version} (if provided)
+    // @Taskline author {name <email>} (if known)
+    // @Taskline created {YYYY-MM-DD}
+
+    let author = resolve_author(author_name, author_email);
+    let created = Local::now().format("%Y-%m-%d").to_string();
+
+    let mut header = format!("@Taskline codename {}\n", filename);
+    if let Some(ver) = &version {
+        header.push_str(&format!("@Taskline version {}\n", ver));
+    }
+    if let Some(author) = &author {
+        header.push_str(&format!("@Taskline author {}\n", author));
+    }
+    header.push_str(&format!("@Taskline created {}\n\n", created));
+    log::debug!("Generated header: '{}'", header.trim());
+
+    // Templates are rendered before the file is created so a bad template leaves nothing behind
+    let content = match template_path {
+        Some(path) => match render_template(path, filename, version.as_ref(), author.as_deref(), &created, &assignments, header) {
+            Ok(content) => content,
+            Err(e) => {
+                log::error!("Failed to render template '{}': {}", path, e);
+                eprintln!("Failed to render template '{}': {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => header,
+    };
+
     log::debug!("File does not exist - proceeding with creation");
 
-    let mut file = match tokio::fs::File::create(&filename_with_extension).await {
+    let mut file = match std::fs::File::create(&filename_with_extension) {
         Ok(f) => {
             log::info!("File '{}' created successfully", filename_with_extension);
             f
@@ -197,23 +283,9 @@ async fn main() {
         }
     };
 
-    // The following content has to be written to the file
-    // @Taskline codename {filename}
-    // @Taskline version {version} (if provided)
-
-    let header = if let Some(ver) = version {
-        let h = format!("@Taskline codename {}\n@Taskline version {}\n\n", filename, ver);
-        log::debug!("Generated header with version: '{}'", h.trim());
-        h
-    } else {
-        let h = format!("@Taskline codename {}\n\n", filename);
-        log::debug!("Generated header without version: '{}'", h.trim());
-        h
-    };
-
-    log::trace!("Writing header to file (length: {} bytes)", header.len());
+    log::trace!("Writing content to file (length: {} bytes)", content.len());
 
-    if let Err(e) = file.write_all(header.as_bytes()).await {
+    if let Err(e) = file.write_all(content.as_bytes()) {
         log::error!("Failed to write to file '{}': {}", filename_with_extension, e);
         eprintln!("Failed to initialize '{}': {}", filename_with_extension, e);
         std::process::exit(1);
@@ -250,19 +322,22 @@ mod tests {
         
         // Valid version formats
         log::debug!("Testing valid version: v1.0.0");
-        assert!(validate_version("v1.0.0").await.is_ok());
+        assert!(validate_version("v1.0.0").is_ok());
         
         log::debug!("Testing valid version: v0.0.1");
-        assert!(validate_version("v0.0.1").await.is_ok());
+        assert!(validate_version("v0.0.1").is_ok());
         
         log::debug!("Testing valid version: v10.20.30");
-        assert!(validate_version("v10.20.30").await.is_ok());
+        assert!(validate_version("v10.20.30").is_ok());
         
         log::debug!("Testing valid version: v999.999.999");
-        assert!(validate_version("v999.999.999").await.is_ok());
+        assert!(validate_version("v999.999.999").is_ok());
         
         log::debug!("Testing valid version: v1.0.0 (duplicate)");
-        assert!(validate_version("v1.0.0").await.is_ok());
+        assert!(validate_version("v1.0.0").is_ok());
+        
+        // The 'v' prefix is optional; output is always canonical
+        assert_eq!(validate_version("1.0.0").unwrap().to_string(), "v1.0.0");
         
         log::info!("test_validate_version_valid_cases completed successfully");
     }
@@ -273,38 +348,35 @@ mod tests {
         log::info!("Starting test_validate_version_invalid_cases");
         
         // Invalid formats
-        log::debug!("Testing invalid version: 1.0.0 (missing 'v')");
-        assert!(validate_version("1.0.0").await.is_err()); // Missing 'v'
-        
         log::debug!("Testing invalid version: v1.0 (missing patch)");
-        assert!(validate_version("v1.0").await.is_err()); // Missing patch
+        assert!(validate_version("v1.0").is_err()); // Missing patch
         
         log::debug!("Testing invalid version: v1.0. (empty patch)");
-        assert!(validate_version("v1.0.").await.is_err()); // Empty patch
+        assert!(validate_version("v1.0.").is_err()); // Empty patch
         
         log::debug!("Testing invalid version: v.1.0 (empty major)");
-        assert!(validate_version("v.1.0").await.is_err()); // Empty major
+        assert!(validate_version("v.1.0").is_err()); // Empty major
         
         log::debug!("Testing invalid version: v1..0 (empty minor)");
-        assert!(validate_version("v1..0").await.is_err()); // Empty minor
+        assert!(validate_version("v1..0").is_err()); // Empty minor
         
         log::debug!("Testing invalid version: v1.0.0.1 (too many parts)");
-        assert!(validate_version("v1.0.0.1").await.is_err()); // Too many parts
+        assert!(validate_version("v1.0.0.1").is_err()); // Too many parts
         
         log::debug!("Testing invalid version: va.b.c (non-numeric)");
-        assert!(validate_version("va.b.c").await.is_err()); // Non-numeric
+        assert!(validate_version("va.b.c").is_err()); // Non-numeric
         
         log::debug!("Testing invalid version: v1.2.3a (invalid character)");
-        assert!(validate_version("v1.2.3a").await.is_err()); // Invalid character
+        assert!(validate_version("v1.2.3a").is_err()); // Invalid character
         
         log::debug!("Testing invalid version: '' (empty)");
-        assert!(validate_version("").await.is_err()); // Empty
+        assert!(validate_version("").is_err()); // Empty
         
         log::debug!("Testing invalid version: 'v' (too short)");
-        assert!(validate_version("v").await.is_err()); // Too short
+        assert!(validate_version("v").is_err()); // Too short
         
         log::debug!("Testing invalid version: 'v1' (too short)");
-        assert!(validate_version("v1").await.is_err()); // Too short
+        assert!(validate_version("v1").is_err()); // Too short
         
         log::info!("test_validate_version_invalid_cases completed successfully");
     }
@@ -316,23 +388,23 @@ mod tests {
         
         // Edge cases
         log::debug!("Testing valid edge case: 'v0.0.0' (all zeros)");
-        assert!(validate_version("v0.0.0").await.is_ok()); // All zeros
+        assert!(validate_version("v0.0.0").is_ok()); // All zeros
         
         log::debug!("Testing valid edge case: 'v1.2.3' (standard format)");
-        assert!(validate_version("v1.2.3").await.is_ok()); // Standard format
+        assert!(validate_version("v1.2.3").is_ok()); // Standard format
         
         log::debug!("Testing valid edge case: 'v100.200.300' (large numbers)");
-        assert!(validate_version("v100.200.300").await.is_ok()); // Large numbers
+        assert!(validate_version("v100.200.300").is_ok()); // Large numbers
         
         // Invalid edge cases
         log::debug!("Testing invalid edge case: 'v1.2.3.' (trailing dot)");
-        assert!(validate_version("v1.2.3.").await.is_err()); // Trailing dot
+        assert!(validate_version("v1.2.3.").is_err()); // Trailing dot
         
         log::debug!("Testing invalid edge case: 'v.' (just v and dot)");
-        assert!(validate_version("v.").await.is_err()); // Just v and dot
+        assert!(validate_version("v.").is_err()); // Just v and dot
         
         log::debug!("Testing invalid edge case: 'v..' (double dots at start)");
-        assert!(validate_version("v..").await.is_err()); // Double dots at start
+        assert!(validate_version("v..").is_err()); // Double dots at start
         
         log::info!("test_validate_version_edge_cases completed successfully");
     }
@@ -389,7 +461,7 @@ mod tests {
         
         // Validate version first
         log::trace!("Validating version: '{}'", test_version);
-        assert!(validate_version(test_version).await.is_ok());
+        assert!(validate_version(test_version).is_ok());
         log::debug!("Version validation passed");
         
         // Simulate the file creation logic from main
@@ -459,8 +531,8 @@ mod tests {
         log::debug!("Starting 20,000 version validations (10k valid, 10k invalid)");
         
         for i in 0..10000 {
-            let _ = validate_version("v1.2.3").await;
-            let _ = validate_version("invalid").await;
+            let _ = validate_version("v1.2.3");
+            let _ = validate_version("invalid");
             
             if i % 2000 == 0 {
                 log::trace!("Completed {} validation cycles", i);
@@ -496,7 +568,7 @@ mod tests {
             }
             
             // Validate version
-            validate_version(&version).await.unwrap();
+            validate_version(&version).unwrap();
             
             // Create filename
             let filename_with_extension = format!("{}.{}.tskln", filename, version);
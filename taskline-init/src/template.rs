@@ -0,0 +1,197 @@
+// File: taskline-init/src/template.rs
+// --- Minimal `{{variable}}` renderer for init templates
+// --- Single pass over the template: `:-` defaults and `:?` required variables, no escaping
+
+use std::collections::HashMap;
+
+/// Variables available to a template
+#[derive(Debug, Default)]
+pub struct TemplateVars {
+    vars: HashMap<String, String>,
+}
+
+impl TemplateVars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a variable, replacing any previous value
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        self.vars.insert(key.to_string(), value.into());
+    }
+
+    /// Parse a `--var key=value` argument
+    pub fn set_assignment(&mut self, assignment: &str) -> Result<(), String> {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid --var '{}', expected key=value", assignment))?;
+        let key = key.trim();
+        if !is_valid_name(key) {
+            return Err(format!("Invalid variable name '{}' in --var", key));
+        }
+        self.set(key, value);
+        Ok(())
+    }
+}
+
+/// Render a template. Grammar (spaces around names and text are ignored):
+///
+/// ```text
+/// placeholder := "{{" name "}}"             the variable; an error when it is not set
+///              | "{{" name ":-" text "}}"   the variable, or `text` when unset or empty
+///              | "{{" name ":?" text "}}"   the variable, or an error saying `text`
+/// text        := any characters and placeholders, up to the matching "}}"
+/// ```
+///
+/// `text` is only expanded when it is used, so `{{owner:-{{team}}}}` needs `team` only
+/// when `owner` is unset. Errors name the line and column of the placeholder at fault.
+pub fn render(template: &str, vars: &TemplateVars) -> Result<String, String> {
+    let mut renderer = Renderer { template, pos: 0, vars };
+    renderer.text(false, true).map_err(|(offset, message)| {
+        let before = &template[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rfind('\n').map_or(before, |newline| &before[newline + 1..]).chars().count() + 1;
+        format!("line {}, column {}: {}", line, column, message)
+    })
+}
+
+/// Byte offset of the failing placeholder and what went wrong
+type RenderError = (usize, String);
+
+struct Renderer<'a> {
+    template: &'a str,
+    pos: usize,
+    vars: &'a TemplateVars,
+}
+
+impl Renderer<'_> {
+    /// Text up to the end of the template or, when `nested` in a placeholder, up to its
+    /// closing `}}` (left unconsumed). Unless `expand`, placeholders are checked but not
+    /// looked up.
+    fn text(&mut self, nested: bool, expand: bool) -> Result<String, RenderError> {
+        let mut output = String::new();
+        loop {
+            let rest = &self.template[self.pos..];
+            let open = rest.find("{{");
+            let close = if nested { rest.find("}}") } else { None };
+            match (open, close) {
+                (Some(open), close) if close.map_or(true, |close| open < close) => {
+                    output.push_str(&rest[..open]);
+                    self.pos += open;
+                    output.push_str(&self.placeholder(expand)?);
+                }
+                (_, Some(close)) => {
+                    output.push_str(&rest[..close]);
+                    self.pos += close;
+                    return Ok(output);
+                }
+                _ => {
+                    output.push_str(rest);
+                    self.pos = self.template.len();
+                    return Ok(output);
+                }
+            }
+        }
+    }
+
+    /// One placeholder starting at `self.pos`
+    fn placeholder(&mut self, expand: bool) -> Result<String, RenderError> {
+        let start = self.pos;
+        let rest = &self.template[start + 2..];
+        let name_end = rest.find(['}', ':', '{']).unwrap_or(rest.len());
+        let name = rest[..name_end].trim();
+        let after = &rest[name_end..];
+        let operator = [":-", ":?", "}}"].into_iter().find(|op| after.starts_with(op));
+        let Some(operator) = operator.filter(|_| is_valid_name(name)) else {
+            return Err(match rest.find("}}") {
+                Some(end) => (start, format!("Invalid placeholder '{{{{{}}}}}' in template", &rest[..end])),
+                None => (start, "Unclosed '{{' in template".to_string()),
+            });
+        };
+        self.pos = start + 2 + name_end + 2;
+        let value = self.vars.vars.get(name).filter(|value| operator == "}}" || !value.is_empty());
+
+        if operator == "}}" {
+            return match value {
+                Some(value) => Ok(value.clone()),
+                None if !expand => Ok(String::new()),
+                None => Err((start, unset(name))),
+            };
+        }
+        let text = self.text(true, expand && value.is_none())?;
+        if !self.template[self.pos..].starts_with("}}") {
+            return Err((start, "Unclosed '{{' in template".to_string()));
+        }
+        self.pos += 2;
+        match (value, operator) {
+            (Some(value), _) => Ok(value.clone()),
+            (None, _) if !expand => Ok(String::new()),
+            (None, ":-") => Ok(text.trim().to_string()),
+            (None, _) if text.trim().is_empty() => Err((start, unset(name))),
+            (None, _) => Err((start, format!("Template variable '{}' is not set: {}", name, text.trim()))),
+        }
+    }
+}
+
+fn unset(name: &str) -> String {
+    format!("Template variable '{}' is not set (pass --var {}=...)", name, name)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        let mut vars = TemplateVars::new();
+        vars.set("codename", "deploy");
+        vars.set("version", "v1.0.0");
+        vars
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let rendered = render("@Taskline codename {{codename}}\n@Taskline version {{ version }}\n", &vars()).unwrap();
+        assert_eq!(rendered, "@Taskline codename deploy\n@Taskline version v1.0.0\n");
+        assert_eq!(render("no placeholders", &vars()).unwrap(), "no placeholders");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        assert!(render("{{missing}}", &vars()).unwrap_err().contains("missing"));
+        assert!(render("{{codename", &vars()).is_err());
+        assert!(render("{{}}", &vars()).is_err());
+    }
+
+    #[test]
+    fn test_render_defaults_and_required() {
+        let mut vars = vars();
+        vars.set("empty", "");
+        assert_eq!(render("{{team:-platform}}/{{ codename :- x }}/{{empty:-fallback}}", &vars).unwrap(), "platform/deploy/fallback");
+        assert_eq!(render("{{owner:-{{team:-{{codename}}}}}}", &vars).unwrap(), "deploy");
+        assert_eq!(render("{{codename:-{{unset}}}} {{codename:?{{unset}}}}", &vars).unwrap(), "deploy deploy");
+        assert_eq!(render("{{team:-}}|", &vars).unwrap(), "|");
+
+        let error = render("line one\n  {{team:?pass --var team=<owner>}}", &vars).unwrap_err();
+        assert_eq!(error, "line 2, column 3: Template variable 'team' is not set: pass --var team=<owner>");
+        assert_eq!(render("{{empty:?}}", &vars).unwrap_err(), "line 1, column 1: Template variable 'empty' is not set (pass --var empty=...)");
+        assert_eq!(render("é {{a:-{{b}}}}", &vars).unwrap_err(), "line 1, column 8: Template variable 'b' is not set (pass --var b=...)");
+        assert_eq!(render("x\n{{a:-{{b:-c}}", &vars).unwrap_err(), "line 2, column 1: Unclosed '{{' in template");
+        assert_eq!(render("{{a b}}", &vars).unwrap_err(), "line 1, column 1: Invalid placeholder '{{a b}}' in template");
+        assert!(render("{{a:+b}}", &vars).is_err());
+    }
+
+    #[test]
+    fn test_var_assignments() {
+        let mut vars = vars();
+        vars.set_assignment("team=platform").unwrap();
+        vars.set_assignment("codename=override").unwrap();
+        vars.set_assignment("url=https://x/?a=b").unwrap();
+        assert_eq!(render("{{team}} {{codename}} {{url}}", &vars).unwrap(), "platform override https://x/?a=b");
+        assert!(vars.set_assignment("novalue").is_err());
+        assert!(vars.set_assignment("bad key=1").is_err());
+    }
+}
@@ -0,0 +1,260 @@
+// File: taskline-py/src/lib.rs
+// --- pyo3 bindings exposing taskline-core as the `taskline` Python module
+// --- Built into a wheel with maturin (see pyproject.toml)
+
+//! Python bindings over [`taskline_core`].
+
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+use taskline_core::{TasklineError, TasklineMetadata, VersionReq};
+
+fn to_py_err(error: TasklineError) -> PyErr {
+    match error {
+        TasklineError::FileError(_) => PyOSError::new_err(error.to_string()),
+        _ => PyValueError::new_err(error.to_string()),
+    }
+}
+
+/// A `vX.Y.Z` script version
+#[pyclass(module = "taskline", frozen, eq, ord, hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Version {
+    inner: taskline_core::Version,
+}
+
+#[pymethods]
+impl Version {
+    #[new]
+    fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { inner: taskline_core::Version::new(major, minor, patch) }
+    }
+
+    /// Parse `vX.Y.Z`; raises ValueError when malformed
+    #[staticmethod]
+    fn parse(version: &str) -> PyResult<Self> {
+        taskline_core::Version::parse(version).map(|inner| Self { inner }).map_err(to_py_err)
+    }
+
+    #[getter]
+    fn major(&self) -> u32 {
+        self.inner.major
+    }
+
+    #[getter]
+    fn minor(&self) -> u32 {
+        self.inner.minor
+    }
+
+    #[getter]
+    fn patch(&self) -> u32 {
+        self.inner.patch
+    }
+
+    fn bump_major(&self) -> PyResult<Self> {
+        self.inner.bump_major().map(|inner| Self { inner }).map_err(to_py_err)
+    }
+
+    fn bump_minor(&self) -> PyResult<Self> {
+        self.inner.bump_minor().map(|inner| Self { inner }).map_err(to_py_err)
+    }
+
+    fn bump_patch(&self) -> PyResult<Self> {
+        self.inner.bump_patch().map(|inner| Self { inner }).map_err(to_py_err)
+    }
+
+    /// Whether this version satisfies a requirement such as `^2.1` or `>=1.0, <2`
+    fn matches(&self, requirement: &str) -> PyResult<bool> {
+        Ok(VersionReq::parse(requirement).map_err(to_py_err)?.matches(&self.inner))
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Version('{}')", self.inner)
+    }
+}
+
+/// Parsed `@Taskline` header; fields are writable and `to_header()` renders them back
+#[pyclass(module = "taskline")]
+struct Metadata {
+    inner: TasklineMetadata,
+}
+
+#[pymethods]
+impl Metadata {
+    #[getter]
+    fn codename(&self) -> &str {
+        &self.inner.codename
+    }
+
+    #[setter]
+    fn set_codename(&mut self, codename: String) {
+        self.inner.codename = codename;
+    }
+
+    #[getter]
+    fn version(&self) -> Option<Version> {
+        self.inner.version.map(|inner| Version { inner })
+    }
+
+    #[setter]
+    fn set_version(&mut self, version: Option<Version>) {
+        self.inner.version = version.map(|v| v.inner);
+    }
+
+    /// `@Taskline author` entries as `(name, email or None)` tuples
+    #[getter]
+    fn authors(&self) -> Vec<(String, Option<String>)> {
+        self.inner.authors.iter().map(|a| (a.name.clone(), a.email.clone())).collect()
+    }
+
+    #[getter]
+    fn created(&self) -> Option<&str> {
+        self.inner.created.as_deref()
+    }
+
+    /// `@Taskline expires` date (`YYYY-MM-DD`)
+    #[getter]
+    fn expires(&self) -> Option<&str> {
+        self.inner.expires.as_deref()
+    }
+
+    #[getter]
+    fn license(&self) -> Option<&str> {
+        self.inner.license.as_deref()
+    }
+
+    /// `@Taskline deprecated` message; `""` when deprecated without one, `None` otherwise
+    #[getter]
+    fn deprecated(&self) -> Option<&str> {
+        self.inner.deprecated.as_deref()
+    }
+
+    #[getter]
+    fn path(&self) -> Vec<String> {
+        self.inner.path.clone()
+    }
+
+    #[getter]
+    fn requires(&self) -> Vec<String> {
+        self.inner.requires.clone()
+    }
+
+    /// Raw `@Taskline requires-taskline` value
+    #[getter]
+    fn requires_taskline(&self) -> Option<&str> {
+        self.inner.requires_taskline.as_deref()
+    }
+
+    /// Render the header block (ends with a blank line)
+    fn to_header(&self) -> String {
+        self.inner.to_header()
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.inner.version {
+            Some(version) => format!("Metadata(codename='{}', version='{}')", self.inner.codename, version),
+            None => format!("Metadata(codename='{}')", self.inner.codename),
+        }
+    }
+}
+
+/// Parse script content leniently; unknown or invalid lines are skipped
+#[pyfunction]
+fn parse(content: &str) -> Metadata {
+    Metadata { inner: TasklineMetadata::parse(content) }
+}
+
+/// Parse script content, raising ValueError on a missing codename, invalid version or bad `requires`
+#[pyfunction]
+fn validate(content: &str) -> PyResult<Metadata> {
+    TasklineMetadata::validate(content).map(|inner| Metadata { inner }).map_err(to_py_err)
+}
+
+/// Read and parse a `.tskln` file
+#[pyfunction]
+fn read(path: std::path::PathBuf) -> PyResult<Metadata> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| PyOSError::new_err(format!("{}: {}", path.display(), e)))?;
+    Ok(parse(&content))
+}
+
+#[pymodule]
+#[pyo3(name = "taskline")]
+fn taskline_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Version>()?;
+    m.add_class::<Metadata>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add_function(wrap_pyfunction!(read, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    /// Run a Python snippet against a fresh `taskline` module bound to the name `taskline`
+    fn run(code: &str) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "taskline").unwrap();
+            taskline_module(&module).unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("taskline", module).unwrap();
+            let code = std::ffi::CString::new(code).unwrap();
+            if let Err(e) = py.run(&code, Some(&globals), None) {
+                panic!("{}", e);
+            }
+        });
+    }
+
+    #[test]
+    fn test_version() {
+        run(r#"
+v = taskline.Version.parse("v1.2.3")
+assert (v.major, v.minor, v.patch) == (1, 2, 3)
+assert str(v.bump_minor()) == "v1.3.0"
+assert v < taskline.Version(1, 10, 0)
+assert v.matches("^1.2") and not v.matches(">=2")
+try:
+    taskline.Version.parse("1.2")
+    raise AssertionError("expected ValueError")
+except ValueError:
+    pass
+"#);
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        run(r#"
+meta = taskline.parse("@Taskline codename deploy\n@Taskline version v1.0.0\n@Taskline requires git\n\nbody\n")
+assert meta.codename == "deploy"
+assert meta.authors == []
+assert taskline.parse("@Taskline author Ada <ada@example.com>\n@Taskline author Ops\n").authors == [("Ada", "ada@example.com"), ("Ops", None)]
+assert meta.requires == ["git"]
+meta.version = meta.version.bump_major()
+assert meta.to_header() == "@Taskline codename deploy\n@Taskline version v2.0.0\n@Taskline requires git\n\n"
+try:
+    taskline.validate("@Taskline version v1.0.0\n")
+    raise AssertionError("expected ValueError")
+except ValueError:
+    pass
+"#);
+    }
+
+    #[test]
+    fn test_read_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("deploy.tskln");
+        std::fs::write(&path, "@Taskline codename deploy\n\n").unwrap();
+        run(&format!(
+            "assert taskline.read({:?}).codename == 'deploy'\ntry:\n    taskline.read({:?})\n    raise AssertionError\nexcept OSError:\n    pass\n",
+            path.to_str().unwrap(),
+            temp_dir.path().join("missing.tskln").to_str().unwrap(),
+        ));
+    }
+}